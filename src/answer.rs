@@ -0,0 +1,123 @@
+use std::fmt;
+
+/// The answer to one part of a puzzle.
+///
+/// Numeric answers compare equal regardless of which integer variant holds them, i.e.
+/// `Answer::Signed(5) == Answer::Unsigned(5)`.
+#[derive(Debug, Clone, Default)]
+pub enum Answer {
+    Signed(i64),
+    Unsigned(u64),
+    U128(u128),
+    Text(String),
+    #[default]
+    Unsolved,
+}
+
+impl Answer {
+    pub fn is_solved(&self) -> bool {
+        !matches!(self, Self::Unsolved)
+    }
+
+    /// Sign and magnitude of numeric answers.
+    fn numeric(&self) -> Option<(bool, u128)> {
+        match self {
+            Self::Signed(v) => Some((v.is_negative(), u128::from(v.unsigned_abs()))),
+            Self::Unsigned(v) => Some((false, u128::from(*v))),
+            Self::U128(v) => Some((false, *v)),
+            Self::Text(_) | Self::Unsolved => None,
+        }
+    }
+}
+
+impl fmt::Display for Answer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Signed(v) => write!(f, "{v}"),
+            Self::Unsigned(v) => write!(f, "{v}"),
+            Self::U128(v) => write!(f, "{v}"),
+            Self::Text(v) => write!(f, "{v}"),
+            Self::Unsolved => write!(f, "unsolved"),
+        }
+    }
+}
+
+impl PartialEq for Answer {
+    fn eq(&self, other: &Self) -> bool {
+        match (self, other) {
+            (Self::Text(a), Self::Text(b)) => a == b,
+            (Self::Unsolved, Self::Unsolved) => true,
+            _ => self.numeric().is_some() && self.numeric() == other.numeric(),
+        }
+    }
+}
+
+impl Eq for Answer {}
+
+macro_rules! define_from {
+    ($variant:ident, $target:ident, $($T:ident),*) => {
+        $(
+            impl From<$T> for Answer {
+                fn from(value: $T) -> Self {
+                    Self::$variant(value as $target)
+                }
+            }
+
+            impl PartialEq<$T> for Answer {
+                fn eq(&self, other: &$T) -> bool {
+                    self.numeric() == Self::$variant(*other as $target).numeric()
+                }
+            }
+        )*
+    };
+}
+
+define_from!(Signed, i64, i8, i16, i32, i64, isize);
+define_from!(Unsigned, u64, u8, u16, u32, u64, usize);
+define_from!(U128, u128, u128);
+
+impl From<String> for Answer {
+    fn from(value: String) -> Self {
+        Self::Text(value)
+    }
+}
+
+impl From<&str> for Answer {
+    fn from(value: &str) -> Self {
+        Self::Text(value.to_owned())
+    }
+}
+
+impl PartialEq<&str> for Answer {
+    fn eq(&self, other: &&str) -> bool {
+        matches!(self, Self::Text(v) if v == other)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Answer;
+
+    #[test]
+    fn test_display() {
+        assert_eq!(Answer::from(-12_i64).to_string(), "-12");
+        assert_eq!(Answer::from(12_usize).to_string(), "12");
+        assert_eq!(Answer::from(u128::MAX).to_string(), u128::MAX.to_string());
+        assert_eq!(Answer::from("4,6,3,5").to_string(), "4,6,3,5");
+        assert_eq!(Answer::Unsolved.to_string(), "unsolved");
+    }
+
+    #[test]
+    fn test_eq() {
+        assert_eq!(Answer::Signed(5), Answer::Unsigned(5));
+        assert_eq!(Answer::U128(5), Answer::Signed(5));
+        assert_ne!(Answer::Signed(-5), Answer::Unsigned(5));
+        assert_ne!(Answer::Text("5".to_owned()), Answer::Unsigned(5));
+        assert_ne!(Answer::Unsolved, Answer::Unsigned(0));
+        assert_eq!(Answer::Unsolved, Answer::Unsolved);
+
+        assert_eq!(Answer::Unsigned(7885693428401), 7885693428401_u64);
+        assert_eq!(Answer::Signed(-3), -3);
+        assert_eq!(Answer::from("abc"), "abc");
+    }
+}
@@ -0,0 +1,234 @@
+use std::collections::BTreeMap;
+use std::fmt::{self, Write};
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use crate::{try_parse, Answer, ParseError, Part};
+
+/// The default answers file, relative to the crate root.
+pub const ANSWERS_FILE: &str = "answers.toml";
+
+/// The absolute path of [`ANSWERS_FILE`], the same regardless of the working directory.
+pub fn path() -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR")).join(ANSWERS_FILE)
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Expected {
+    Known(Answer),
+    Unknown,
+}
+
+/// Verified puzzle answers stored in a small TOML file.
+///
+/// ```toml
+/// [day01]
+/// part1 = 2066446
+/// part2 = "4,6,3,5"
+/// ```
+///
+/// Only the subset of TOML above is supported: `[dayNN]` tables with `part1`/`part2` keys whose
+/// values are integers or basic strings. Parts without an entry are [`Expected::Unknown`].
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Answers {
+    answers: BTreeMap<(u8, Part), Answer>,
+}
+
+impl Answers {
+    /// Load answers from `path`, a missing file has no known answers.
+    pub fn load(path: impl AsRef<Path>) -> io::Result<Self> {
+        match fs::read_to_string(path) {
+            Ok(contents) => {
+                Self::parse(&contents).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+            }
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(Self::default()),
+            Err(e) => Err(e),
+        }
+    }
+
+    pub fn save(&self, path: impl AsRef<Path>) -> io::Result<()> {
+        fs::write(path, self.to_string())
+    }
+
+    pub fn parse(input: &str) -> Result<Self, ParseError> {
+        let mut answers = Self::default();
+        let mut day = None;
+
+        for line in input.lines().map(str::trim) {
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+
+            if let Some(table) = line.strip_prefix('[') {
+                let name = table
+                    .strip_suffix(']')
+                    .ok_or_else(|| ParseError::at(input, line, "table header `[dayNN]`"))?;
+                let number = name
+                    .strip_prefix("day")
+                    .ok_or_else(|| ParseError::at(input, name, "table named `dayNN`"))?;
+                day = Some(try_parse::<u8>(input, number)?);
+                continue;
+            }
+
+            let (key, value) = line
+                .split_once('=')
+                .ok_or_else(|| ParseError::at(input, line, "`key = value`"))?;
+            let (key, value) = (key.trim(), value.trim());
+            let day = day.ok_or_else(|| ParseError::at(input, line, "`[dayNN]` table header"))?;
+            let part = match key {
+                "part1" => Part::One,
+                "part2" => Part::Two,
+                _ => return Err(ParseError::at(input, key, "`part1` or `part2`")),
+            };
+
+            answers
+                .answers
+                .insert((day, part), parse_value(input, value)?);
+        }
+
+        Ok(answers)
+    }
+
+    pub fn get(&self, day: u8, part: Part) -> Expected {
+        self.answers
+            .get(&(day, part))
+            .cloned()
+            .map_or(Expected::Unknown, Expected::Known)
+    }
+
+    /// Record the verified `answer` for `day` and `part`, replacing any previous answer.
+    ///
+    /// **Note:** Panics if `answer` is [`Answer::Unsolved`]
+    pub fn record(&mut self, day: u8, part: Part, answer: Answer) {
+        assert!(answer.is_solved(), "Cannot record an unsolved answer");
+
+        self.answers.insert((day, part), answer);
+    }
+}
+
+impl fmt::Display for Answers {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut current = None;
+
+        for ((day, part), answer) in &self.answers {
+            if current != Some(*day) {
+                if current.is_some() {
+                    writeln!(f)?;
+                }
+                writeln!(f, "[day{day:02}]")?;
+                current = Some(*day);
+            }
+
+            write!(f, "part{part} = ")?;
+            match answer {
+                Answer::Text(text) => {
+                    f.write_char('"')?;
+                    for c in text.chars() {
+                        if matches!(c, '"' | '\\') {
+                            f.write_char('\\')?;
+                        }
+                        f.write_char(c)?;
+                    }
+                    writeln!(f, "\"")?;
+                }
+                answer => writeln!(f, "{answer}")?,
+            }
+        }
+
+        Ok(())
+    }
+}
+
+fn parse_value(input: &str, value: &str) -> Result<Answer, ParseError> {
+    if let Some(quoted) = value.strip_prefix('"') {
+        let quoted = quoted
+            .strip_suffix('"')
+            .ok_or_else(|| ParseError::at(input, value, "closing `\"`"))?;
+
+        let mut text = String::with_capacity(quoted.len());
+        let mut chars = quoted.chars();
+        while let Some(c) = chars.next() {
+            match c {
+                '\\' => match chars.next() {
+                    Some(escaped @ ('"' | '\\')) => text.push(escaped),
+                    _ => return Err(ParseError::at(input, value, "`\\\"` or `\\\\` escape")),
+                },
+                c => text.push(c),
+            }
+        }
+
+        return Ok(Answer::Text(text));
+    }
+
+    if value.starts_with('-') {
+        return try_parse::<i64>(input, value).map(Answer::from);
+    }
+
+    let value = try_parse::<u128>(input, value)?;
+
+    Ok(u64::try_from(value).map_or(Answer::U128(value), Answer::from))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{path, Answers, Expected, ANSWERS_FILE};
+    use crate::{Answer, ParseError, Part};
+
+    const INPUT: &str = r#"
+# Comment
+[day01]
+part1 = 2066446
+part2 = -12
+
+[day17]
+part1 = "4,6,\"3\""
+part2 = 340282366920938463463374607431768211455
+"#;
+
+    #[test]
+    fn test_parse() {
+        let answers = Answers::parse(INPUT).unwrap();
+
+        assert_eq!(answers.get(1, Part::One), Expected::Known(2066446.into()));
+        assert_eq!(answers.get(1, Part::Two), Expected::Known((-12).into()));
+        assert_eq!(
+            answers.get(17, Part::One),
+            Expected::Known("4,6,\"3\"".into())
+        );
+        assert_eq!(
+            answers.get(17, Part::Two),
+            Expected::Known(u128::MAX.into())
+        );
+        assert_eq!(answers.get(2, Part::One), Expected::Unknown);
+    }
+
+    #[test]
+    fn test_parse_error() {
+        assert_eq!(
+            Answers::parse("[day01]\npart3 = 1").err(),
+            Some(ParseError::new(2, 1, "part3", "`part1` or `part2`"))
+        );
+        assert!(Answers::parse("part1 = 1").is_err());
+        assert!(Answers::parse("[day01]\npart1 = \"abc").is_err());
+    }
+
+    #[test]
+    fn test_round_trip() {
+        let mut answers = Answers::parse(INPUT).unwrap();
+        answers.record(9, Part::Two, Answer::Unsigned(42));
+
+        let serialized = answers.to_string();
+        assert!(serialized.contains("[day09]\npart2 = 42\n"));
+        assert_eq!(Answers::parse(&serialized), Ok(answers));
+    }
+
+    #[test]
+    fn test_path() {
+        let path = path();
+
+        assert!(path.is_absolute());
+        assert!(path.ends_with(ANSWERS_FILE));
+        assert!(path.parent().unwrap().join("Cargo.toml").is_file());
+    }
+}
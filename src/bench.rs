@@ -0,0 +1,303 @@
+use std::fmt::Write;
+use std::time::{Duration, Instant};
+
+use crate::{memo, DynSolution, ParseError, Part};
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct Config {
+    /// Untimed runs of every phase before measuring.
+    pub warmup: usize,
+    /// Timed runs of every phase.
+    pub iterations: usize,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Self {
+            warmup: 3,
+            iterations: 20,
+        }
+    }
+}
+
+/// Summary statistics over a set of timing samples.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct Stats {
+    pub min: Duration,
+    pub median: Duration,
+    pub mean: Duration,
+    /// Sample standard deviation.
+    pub stddev: Duration,
+}
+
+impl Stats {
+    /// **Note:** Panics if `samples` is empty
+    pub fn from_samples(samples: &[Duration]) -> Self {
+        assert!(!samples.is_empty(), "Need at least one sample");
+
+        let mut sorted = samples.to_vec();
+        sorted.sort();
+
+        let n = sorted.len();
+        let median = if n.is_multiple_of(2) {
+            (sorted[n / 2 - 1] + sorted[n / 2]) / 2
+        } else {
+            sorted[n / 2]
+        };
+
+        let nanos = || sorted.iter().map(|d| d.as_nanos() as f64);
+        let mean = nanos().sum::<f64>() / n as f64;
+        let variance = if n > 1 {
+            nanos().map(|s| (s - mean).powi(2)).sum::<f64>() / (n - 1) as f64
+        } else {
+            0.0
+        };
+
+        Self {
+            min: sorted[0],
+            median,
+            mean: Duration::from_nanos(mean.round() as u64),
+            stddev: Duration::from_nanos(variance.sqrt().round() as u64),
+        }
+    }
+}
+
+/// Timings for one day, parsing is measured separately from solving.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct Report {
+    pub day: u8,
+    pub parse: Stats,
+    pub part_one: Stats,
+    pub part_two: Stats,
+    /// Memoization statistics of solving both parts once.
+    pub memo: memo::Stats,
+}
+
+impl Report {
+    pub fn phases(&self) -> [(&'static str, &Stats); 3] {
+        [
+            ("parse", &self.parse),
+            ("part_one", &self.part_one),
+            ("part_two", &self.part_two),
+        ]
+    }
+}
+
+/// Run `closure` once and measure how long it took.
+pub fn time<F, R>(closure: F) -> (R, Duration)
+where
+    F: FnOnce() -> R,
+{
+    let start = Instant::now();
+    let result = closure();
+
+    (result, start.elapsed())
+}
+
+/// Benchmark parsing and both parts of `solution` on `input`.
+pub fn bench(
+    day: u8,
+    solution: &dyn DynSolution,
+    input: &str,
+    config: &Config,
+) -> Result<Report, ParseError> {
+    let iterations = config.iterations.max(1);
+    let mut parsed = solution.parse_input(input)?;
+
+    for _ in 0..config.warmup {
+        parsed = solution.parse_input(input)?;
+        for part in Part::BOTH {
+            std::hint::black_box(solution.solve_part(&parsed, part));
+        }
+    }
+
+    let ((), memo) = memo::collect(|| {
+        for part in Part::BOTH {
+            std::hint::black_box(solution.solve_part(&parsed, part));
+        }
+    });
+
+    let mut parse = Vec::with_capacity(iterations);
+    for _ in 0..iterations {
+        let (result, duration) = time(|| solution.parse_input(input));
+        parsed = result?;
+        parse.push(duration);
+    }
+
+    let solve = |part| {
+        (0..iterations)
+            .map(|_| time(|| std::hint::black_box(solution.solve_part(&parsed, part))).1)
+            .collect::<Vec<_>>()
+    };
+    let part_one = solve(Part::One);
+    let part_two = solve(Part::Two);
+
+    Ok(Report {
+        day,
+        parse: Stats::from_samples(&parse),
+        part_one: Stats::from_samples(&part_one),
+        part_two: Stats::from_samples(&part_two),
+        memo,
+    })
+}
+
+/// Render reports as a JSON array, all durations are in nanoseconds.
+pub fn to_json(reports: &[Report]) -> String {
+    let mut out = String::from("[");
+
+    for (i, report) in reports.iter().enumerate() {
+        let separator = if i == 0 { "" } else { "," };
+        write!(out, "{separator}\n  {{\"day\": {}", report.day).unwrap();
+
+        for (name, stats) in report.phases() {
+            write!(
+                out,
+                ", \"{name}\": {{\"min_ns\": {}, \"median_ns\": {}, \"mean_ns\": {}, \"stddev_ns\": {}}}",
+                stats.min.as_nanos(),
+                stats.median.as_nanos(),
+                stats.mean.as_nanos(),
+                stats.stddev.as_nanos()
+            )
+            .unwrap();
+        }
+        write!(
+            out,
+            ", \"memo\": {{\"hits\": {}, \"misses\": {}}}}}",
+            report.memo.hits, report.memo.misses
+        )
+        .unwrap();
+    }
+
+    if !reports.is_empty() {
+        out.push('\n');
+    }
+    out.push_str("]\n");
+
+    out
+}
+
+/// Render reports as a markdown table with one row per day and phase, followed by a table of
+/// memoization statistics for the days that use it.
+pub fn to_markdown(reports: &[Report]) -> String {
+    let mut out = String::from(
+        "| Day | Phase | Min | Median | Mean | Std dev |\n|----:|:------|----:|-------:|-----:|--------:|\n",
+    );
+
+    for report in reports {
+        for (name, stats) in report.phases() {
+            writeln!(
+                out,
+                "| {:02} | {name} | {} | {} | {} | {} |",
+                report.day,
+                format_duration(stats.min),
+                format_duration(stats.median),
+                format_duration(stats.mean),
+                format_duration(stats.stddev)
+            )
+            .unwrap();
+        }
+    }
+
+    let memoized: Vec<_> = reports.iter().filter(|r| r.memo.lookups() > 0).collect();
+    if !memoized.is_empty() {
+        out.push_str(
+            "\n| Day | Memo hits | Memo misses | Hit rate |\n|----:|----------:|------------:|---------:|\n",
+        );
+        for report in memoized {
+            writeln!(
+                out,
+                "| {:02} | {} | {} | {:.1}% |",
+                report.day,
+                report.memo.hits,
+                report.memo.misses,
+                report.memo.hit_rate() * 100.0
+            )
+            .unwrap();
+        }
+    }
+
+    out
+}
+
+pub fn format_duration(duration: Duration) -> String {
+    let nanos = duration.as_nanos();
+
+    if nanos < 1_000 {
+        format!("{nanos}ns")
+    } else if nanos < 1_000_000 {
+        format!("{:.2}µs", duration.as_secs_f64() * 1_000_000.0)
+    } else if nanos < 1_000_000_000 {
+        format!("{:.2}ms", duration.as_secs_f64() * 1_000.0)
+    } else {
+        format!("{:.2}s", duration.as_secs_f64())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::{bench, to_json, to_markdown, Config, Report, Stats};
+    use crate::day01::Day01;
+    use crate::memo;
+
+    fn ms(v: u64) -> Duration {
+        Duration::from_millis(v)
+    }
+
+    #[test]
+    fn test_stats() {
+        let stats = Stats::from_samples(&[ms(4), ms(2), ms(8), ms(6)]);
+
+        assert_eq!(stats.min, ms(2));
+        assert_eq!(stats.median, ms(5));
+        assert_eq!(stats.mean, ms(5));
+        // sqrt(20 / 3) ms
+        assert_eq!(stats.stddev, Duration::from_nanos(2_581_989));
+
+        let stats = Stats::from_samples(&[ms(3)]);
+        assert_eq!(stats.median, ms(3));
+        assert_eq!(stats.stddev, Duration::ZERO);
+    }
+
+    #[test]
+    fn test_bench() {
+        let config = Config {
+            warmup: 1,
+            iterations: 3,
+        };
+        let report = bench(1, &Day01, "3   4\n4   3\n", &config).unwrap();
+
+        assert_eq!(report.day, 1);
+        assert!(bench(1, &Day01, "3   x\n", &config).is_err());
+    }
+
+    #[test]
+    fn test_formats() {
+        let stats = Stats::from_samples(&[ms(2)]);
+        let mut reports = [Report {
+            day: 7,
+            parse: stats,
+            part_one: stats,
+            part_two: stats,
+            memo: memo::Stats::default(),
+        }];
+
+        let json = to_json(&reports);
+        assert!(json.starts_with("[\n  {\"day\": 7, \"parse\": {\"min_ns\": 2000000,"));
+        assert!(json.ends_with("\"stddev_ns\": 0}, \"memo\": {\"hits\": 0, \"misses\": 0}}\n]\n"));
+        assert_eq!(to_json(&[]), "[]\n");
+
+        let markdown = to_markdown(&reports);
+        assert_eq!(markdown.lines().count(), 5);
+        assert_eq!(
+            markdown.lines().nth(3),
+            Some("| 07 | part_one | 2.00ms | 2.00ms | 2.00ms | 0ns |")
+        );
+
+        reports[0].memo = memo::Stats { hits: 3, misses: 1 };
+        let markdown = to_markdown(&reports);
+        assert_eq!(markdown.lines().count(), 9);
+        assert_eq!(markdown.lines().last(), Some("| 07 | 3 | 1 | 75.0% |"));
+    }
+}
@@ -0,0 +1,206 @@
+use std::collections::HashMap;
+use std::hash::Hash;
+
+/// A cycle in a sequence of states `x0, x1 = step(x0), x2 = step(x1), ...`.
+///
+/// The detectors take a `step` that returns `None` once the simulation ends, in which case there
+/// is no cycle.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Cycle {
+    /// The index of the first state that is part of the cycle.
+    pub start: usize,
+    /// The number of states in the cycle.
+    pub length: usize,
+}
+
+impl Cycle {
+    /// The earliest index with the same state as index `n`.
+    pub fn index_of(&self, n: usize) -> usize {
+        if n < self.start {
+            n
+        } else {
+            self.start + (n - self.start) % self.length
+        }
+    }
+}
+
+/// Floyd's tortoise and hare, constant memory but more calls to `step` than [`brent`].
+pub fn floyd<S: Clone + PartialEq>(initial: S, step: impl Fn(&S) -> Option<S>) -> Option<Cycle> {
+    let mut tortoise = step(&initial)?;
+    let mut hare = step(&tortoise)?;
+    while tortoise != hare {
+        tortoise = step(&tortoise)?;
+        hare = step(&step(&hare)?)?;
+    }
+
+    // The distance to the meeting point is a multiple of the cycle length, walking from the
+    // start and the meeting point in lock step they meet at the start of the cycle.
+    let mut start = 0;
+    tortoise = initial;
+    while tortoise != hare {
+        tortoise = step(&tortoise)?;
+        hare = step(&hare)?;
+        start += 1;
+    }
+
+    let mut length = 1;
+    hare = step(&tortoise)?;
+    while tortoise != hare {
+        hare = step(&hare)?;
+        length += 1;
+    }
+
+    Some(Cycle { start, length })
+}
+
+/// Brent's algorithm, constant memory.
+pub fn brent<S: Clone + PartialEq>(initial: S, step: impl Fn(&S) -> Option<S>) -> Option<Cycle> {
+    let mut power = 1;
+    let mut length = 1;
+    let mut tortoise = initial.clone();
+    let mut hare = step(&initial)?;
+    while tortoise != hare {
+        if power == length {
+            tortoise = hare.clone();
+            power *= 2;
+            length = 0;
+        }
+        hare = step(&hare)?;
+        length += 1;
+    }
+
+    // Start the hare `length` states ahead, they meet at the start of the cycle
+    let mut start = 0;
+    tortoise = initial.clone();
+    hare = initial;
+    for _ in 0..length {
+        hare = step(&hare)?;
+    }
+    while tortoise != hare {
+        tortoise = step(&tortoise)?;
+        hare = step(&hare)?;
+        start += 1;
+    }
+
+    Some(Cycle { start, length })
+}
+
+/// Remember every state, calls `step` the fewest times and allows it to have side effects.
+pub fn hashed<S: Clone + Eq + Hash>(
+    initial: S,
+    mut step: impl FnMut(&S) -> Option<S>,
+) -> Option<Cycle> {
+    let mut seen = HashMap::new();
+    let mut state = initial;
+
+    for index in 0.. {
+        if let Some(start) = seen.insert(state.clone(), index) {
+            return Some(Cycle {
+                start,
+                length: index - start,
+            });
+        }
+        state = step(&state)?;
+    }
+
+    unreachable!()
+}
+
+/// The state after `n` steps, skipping over whole cycles once one is found. `None` if the
+/// simulation ends before `n` steps.
+pub fn fast_forward<S: Clone + Eq + Hash>(
+    initial: S,
+    mut step: impl FnMut(&S) -> Option<S>,
+    n: usize,
+) -> Option<S> {
+    let mut seen = HashMap::new();
+    let mut states = vec![];
+    let mut state = initial;
+
+    for index in 0..n {
+        if let Some(start) = seen.insert(state.clone(), index) {
+            let cycle = Cycle {
+                start,
+                length: index - start,
+            };
+
+            return Some(states.swap_remove(cycle.index_of(n)));
+        }
+        states.push(state.clone());
+        state = step(&state)?;
+    }
+
+    Some(state)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{brent, fast_forward, floyd, hashed, Cycle};
+
+    /// 8, 7, 6, 5, 4, 0, 1, 2, 3, 4, 0, ...
+    fn step(x: &u64) -> Option<u64> {
+        Some(if *x > 4 { x - 1 } else { (x + 1) % 5 })
+    }
+
+    #[test]
+    fn test_detectors() {
+        let expected = Some(Cycle {
+            start: 4,
+            length: 5,
+        });
+        assert_eq!(floyd(8, step), expected);
+        assert_eq!(brent(8, step), expected);
+        assert_eq!(hashed(8, step), expected);
+
+        let expected = Some(Cycle {
+            start: 0,
+            length: 5,
+        });
+        assert_eq!(floyd(2, step), expected);
+        assert_eq!(brent(2, step), expected);
+        assert_eq!(hashed(2, step), expected);
+
+        // A fixed point
+        let fixed = Some(Cycle {
+            start: 1,
+            length: 1,
+        });
+        let halve = |x: &u64| Some(x / 2);
+        assert_eq!(floyd(1, halve), fixed);
+        assert_eq!(brent(1, halve), fixed);
+        assert_eq!(hashed(1, halve), fixed);
+
+        for initial in 0..255 {
+            let square = |x: &u64| Some((x * x + 1) % 255);
+            let cycle = hashed(initial, square);
+            assert_eq!(floyd(initial, square), cycle);
+            assert_eq!(brent(initial, square), cycle);
+        }
+    }
+
+    #[test]
+    fn test_terminating() {
+        let count = |x: &u64| (*x < 10).then_some(x + 1);
+
+        assert_eq!(floyd(0, count), None);
+        assert_eq!(brent(0, count), None);
+        assert_eq!(hashed(0, count), None);
+        assert_eq!(fast_forward(0, count, 5), Some(5));
+        assert_eq!(fast_forward(0, count, 11), None);
+    }
+
+    #[test]
+    fn test_fast_forward() {
+        let cycle = Cycle {
+            start: 4,
+            length: 5,
+        };
+        assert_eq!(cycle.index_of(3), 3);
+        assert_eq!(cycle.index_of(9), 4);
+
+        assert_eq!(fast_forward(8, step, 0), Some(8));
+        assert_eq!(fast_forward(8, step, 3), Some(5));
+        assert_eq!(fast_forward(8, step, 1_000_000_000), Some(0));
+        assert_eq!(fast_forward(8, step, 1_000_000_003), Some(3));
+    }
+}
@@ -1,62 +1,108 @@
-use std::collections::HashMap;
+use std::collections::BTreeMap;
+use std::io::BufRead;
+use std::iter;
 
-pub fn star_one(input: &str) -> u64 {
-    let (mut f, mut s) = parse(input);
+use crate::parse::{self, context, integer, lines, separated_pair, spaces1, Parser};
+use crate::stream::{self, StreamError};
+use crate::{Answer, ParseError, Solution};
 
-    f.sort();
-    s.sort();
+pub struct Day01;
 
-    f.into_iter()
-        .zip(s.into_iter())
+/// The number of occurrences of every location ID.
+type Counts = BTreeMap<i64, usize>;
+
+impl Solution for Day01 {
+    /// How often every location ID occurs in the left and right list.
+    type Input = (Counts, Counts);
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        let mut counts = (Counts::new(), Counts::new());
+        for ids in parse::complete(input, lines(location_ids()))? {
+            count(&mut counts, ids);
+        }
+
+        Ok(counts)
+    }
+
+    fn part_one((left, right): &Self::Input) -> Answer {
+        distance(left, right).into()
+    }
+
+    fn part_two((left, right): &Self::Input) -> Answer {
+        similarity(left, right).into()
+    }
+}
+
+pub fn star_one(input: &str) -> Answer {
+    star_one_reader(input.as_bytes()).expect("valid input")
+}
+
+pub fn star_two(input: &str) -> Answer {
+    star_two_reader(input.as_bytes()).expect("valid input")
+}
+
+/// [`star_one`] reading from `reader`, memory is bounded by the number of distinct location IDs.
+pub fn star_one_reader(reader: impl BufRead) -> Result<Answer, StreamError> {
+    Ok(Day01::part_one(&count_ids(reader)?))
+}
+
+/// [`star_two`] reading from `reader`, memory is bounded by the number of distinct location IDs.
+pub fn star_two_reader(reader: impl BufRead) -> Result<Answer, StreamError> {
+    Ok(Day01::part_two(&count_ids(reader)?))
+}
+
+/// The total distance between the lists when both are sorted.
+fn distance(left: &Counts, right: &Counts) -> u64 {
+    sorted(left)
+        .zip(sorted(right))
         .map(|(a, b)| a.abs_diff(b))
         .sum()
 }
 
-pub fn star_two(input: &str) -> i64 {
-    let (f, s) = parse(input);
+/// Every occurrence of every ID in ascending order.
+fn sorted(counts: &Counts) -> impl Iterator<Item = i64> + '_ {
+    counts
+        .iter()
+        .flat_map(|(id, count)| iter::repeat_n(*id, *count))
+}
 
-    let counts = {
-        let mut counts = HashMap::new();
+/// Every ID in the left list times how often it occurs in the right list.
+fn similarity(left: &Counts, right: &Counts) -> i64 {
+    left.iter()
+        .map(|(id, count)| id * (count * right.get(id).unwrap_or(&0)) as i64)
+        .sum()
+}
 
-        for i in &s {
-            let _ = counts.entry(*i).and_modify(|e| *e += 1).or_insert(1);
-        }
-        counts
-    };
+/// [`Day01::parse`] one line at a time.
+fn count_ids(reader: impl BufRead) -> Result<(Counts, Counts), StreamError> {
+    let mut counts = (Counts::new(), Counts::new());
 
-    f.into_iter()
-        .map(|f| f * counts.get(&f).unwrap_or(&0))
-        .sum()
+    stream::for_each_line(reader, |line| {
+        count(&mut counts, parse::complete(line, location_ids())?);
+
+        Ok(())
+    })?;
+
+    Ok(counts)
 }
 
-fn parse(input: &str) -> (Vec<i64>, Vec<i64>) {
-    input
-        .lines()
-        .map(|l| l.trim())
-        .filter(|l| !l.is_empty())
-        .map(|l| {
-            let mut parts = l.split_whitespace();
-            let f = parts
-                .next()
-                .expect("first item is present")
-                .parse::<i64>()
-                .expect("parsable number");
-
-            let s = parts
-                .next()
-                .expect("second item is present")
-                .parse::<i64>()
-                .expect("parsable number");
-
-            (f, s)
-        })
-        .unzip()
+fn count((left, right): &mut (Counts, Counts), (l, r): (i64, i64)) {
+    *left.entry(l).or_default() += 1;
+    *right.entry(r).or_default() += 1;
+}
+
+fn location_ids<'a>() -> impl Parser<'a, (i64, i64)> {
+    context(
+        "two location IDs",
+        separated_pair(integer::<i64>(), spaces1(), integer::<i64>()),
+    )
 }
 
 #[cfg(test)]
 mod tests {
-    use super::{star_one, star_two};
-    const INPUT: &'static str = r#"
+    use super::{star_one, star_one_reader, star_two, star_two_reader, Day01};
+    use crate::{ParseError, Solution};
+    const INPUT: &str = r#"
 3   4
 4   3
 2   5
@@ -74,4 +120,31 @@ mod tests {
     fn test_star_two() {
         assert_eq!(star_two(INPUT), 31);
     }
+
+    #[test]
+    fn test_reader() {
+        let parsed = Day01::parse(INPUT).unwrap();
+
+        assert_eq!(
+            star_one_reader(INPUT.as_bytes()).unwrap(),
+            Day01::part_one(&parsed)
+        );
+        assert_eq!(
+            star_two_reader(INPUT.as_bytes()).unwrap(),
+            Day01::part_two(&parsed)
+        );
+        assert!(star_one_reader("3   4\n4\n".as_bytes()).is_err());
+    }
+
+    #[test]
+    fn test_parse_error() {
+        assert_eq!(
+            Day01::parse("3   4\n4   -\n").err(),
+            Some(ParseError::new(2, 5, "-", "i64"))
+        );
+        assert_eq!(
+            Day01::parse("3   4\n4\n").err(),
+            Some(ParseError::new(2, 1, "4", "two location IDs"))
+        );
+    }
 }
@@ -1,39 +1,86 @@
-pub fn star_one(input: &str) -> usize {
-    let iter = parse(input);
+use std::io::BufRead;
 
-    iter.filter(|l| valid(l, None)).count()
+use crate::parse::{self, integer, lines, separated, spaces1, Parser};
+use crate::stream::{self, StreamError};
+use crate::{Answer, ParseError, Solution};
+
+pub struct Day02;
+
+impl Solution for Day02 {
+    type Input = Vec<Vec<i64>>;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse::complete(input, lines(report()))
+    }
+
+    fn part_one(reports: &Self::Input) -> Answer {
+        reports.iter().filter(|l| valid(l, None)).count().into()
+    }
+
+    fn part_two(reports: &Self::Input) -> Answer {
+        reports.iter().filter(|r| tolerable(r)).count().into()
+    }
 }
 
-pub fn star_two(input: &str) -> usize {
-    let iter = parse(input);
+pub fn star_one(input: &str) -> Answer {
+    star_one_reader(input.as_bytes()).expect("valid input")
+}
 
-    let mut count = 0;
-    for report in iter {
-        if valid(&report, None) {
-            count += 1;
-            continue;
-        };
-        // Brute force
-        let any_valid = (0..report.len()).any(|i| valid(&report, Some(i)));
+pub fn star_two(input: &str) -> Answer {
+    star_two_reader(input.as_bytes()).expect("valid input")
+}
+
+/// [`star_one`] reading from `reader` one report at a time.
+pub fn star_one_reader(reader: impl BufRead) -> Result<Answer, StreamError> {
+    count_reports(reader, |r| valid(r, None))
+}
 
-        if any_valid {
+/// [`star_two`] reading from `reader` one report at a time.
+pub fn star_two_reader(reader: impl BufRead) -> Result<Answer, StreamError> {
+    count_reports(reader, tolerable)
+}
+
+fn count_reports(
+    reader: impl BufRead,
+    check: impl Fn(&[i64]) -> bool,
+) -> Result<Answer, StreamError> {
+    let mut count = 0_usize;
+    stream::for_each_line(reader, |line| {
+        if check(&parse::complete(line, report())?) {
             count += 1;
         }
-    }
 
-    count
+        Ok(())
+    })?;
+
+    Ok(count.into())
 }
 
-fn parse(input: &str) -> impl Iterator<Item = Vec<i64>> + '_ {
-    input
-        .lines()
-        .map(|l| l.trim())
-        .filter(|l| !l.is_empty())
-        .map(|l| {
-            l.split_whitespace()
-                .map(|d| d.parse::<i64>().expect("Parasable digit"))
-                .collect::<Vec<_>>()
-        })
+fn report<'a>() -> impl Parser<'a, Vec<i64>> {
+    separated(integer(), spaces1())
+}
+
+/// Whether the report is valid when removing at most one level.
+fn tolerable(report: &[i64]) -> bool {
+    let Some(i) = first_violation(report) else {
+        return true;
+    };
+
+    // Removing any other level leaves the pair `i`, `i + 1` and the direction it is compared
+    // against intact, `i - 1` matters because the first pair decides the direction
+    (i.saturating_sub(1)..=i + 1).any(|r| valid(report, Some(r)))
+}
+
+/// The index of the first level whose difference to the next isn't safe, `None` if the report is
+/// valid.
+fn first_violation(report: &[i64]) -> Option<usize> {
+    let decreasing = report.len() > 1 && report[0] > report[1];
+
+    report.windows(2).position(|w| {
+        let d = w[0] - w[1];
+
+        !(1..=3).contains(&d.abs()) || d.is_positive() != decreasing
+    })
 }
 
 fn valid(values: &[i64], ignored_idx: Option<usize>) -> bool {
@@ -43,7 +90,7 @@ fn valid(values: &[i64], ignored_idx: Option<usize>) -> bool {
         .filter(|(idx, _)| Some(idx) != ignored_idx.as_ref())
         .zip(
             values
-                .into_iter()
+                .iter()
                 .enumerate()
                 .filter(|(idx, _)| Some(idx) != ignored_idx.as_ref())
                 .skip(1),
@@ -55,7 +102,7 @@ fn valid(values: &[i64], ignored_idx: Option<usize>) -> bool {
             }
             let abs_diff = d.abs();
 
-            if abs_diff < 1 || abs_diff > 3 {
+            if !(1..=3).contains(&abs_diff) {
                 return (false, decreasing);
             }
 
@@ -71,8 +118,10 @@ fn valid(values: &[i64], ignored_idx: Option<usize>) -> bool {
 
 #[cfg(test)]
 mod tests {
-    use super::{star_one, star_two};
-    const INPUT: &'static str = r#"
+    use super::{star_one, star_two, tolerable, valid, Day02};
+    use crate::prop::{self, int, vec, Config};
+    use crate::Solution;
+    const INPUT: &str = r#"
 7 6 4 2 1
 1 2 7 8 9
 9 7 6 2 1
@@ -92,6 +141,14 @@ mod tests {
         assert_eq!(star_two(INPUT), 5)
     }
 
+    #[test]
+    fn test_reader() {
+        let reports = Day02::parse(INPUT).unwrap();
+
+        assert_eq!(star_one(INPUT), Day02::part_one(&reports));
+        assert_eq!(star_two(INPUT), Day02::part_two(&reports));
+    }
+
     #[test]
     fn test_star_two_fails() {
         assert_eq!(
@@ -126,4 +183,28 @@ mod tests {
             11
         )
     }
+
+    /// Reference for [`tolerable`], trying every level for removal.
+    fn tolerable_naive(report: &[i64]) -> bool {
+        valid(report, None) || (0..report.len()).any(|i| valid(report, Some(i)))
+    }
+
+    #[test]
+    fn test_tolerable_matches_naive() {
+        // Mostly safe steps in one direction so that plenty of reports are close to tolerable
+        let steps = (int(0..=1), vec(int(-1..=4), 0..=8));
+
+        prop::assert_holds(Config::cases(10_000), &steps, |(decreasing, steps)| {
+            let sign = if *decreasing == 1 { -1 } else { 1 };
+            let report: Vec<_> = steps
+                .iter()
+                .scan(10, |level, step| {
+                    *level += sign * step;
+                    Some(*level)
+                })
+                .collect();
+
+            tolerable(&report) == tolerable_naive(&report)
+        });
+    }
 }
@@ -1,54 +1,225 @@
-use regex::Regex;
+use std::io::BufRead;
 
-pub fn star_one(input: &str) -> i64 {
-    let re = Regex::new(r"mul\((\d+)\s*,\s*(\d+)\)").unwrap();
+use crate::parse::{self, alt, delimited, map, scan, separated_pair, tag, unsigned, Parser};
+use crate::stream::{self, StreamError};
+use crate::{Answer, ParseError, Solution};
 
-    re.captures_iter(input)
-        .map(|c| {
-            let lhs = c.get(1).expect("left capture");
-            let rhs = c.get(2).expect("right capture");
+pub struct Day03;
 
-            let lhs = lhs.as_str().parse::<i64>().expect("lhs parse");
-            let rhs = rhs.as_str().parse::<i64>().expect("rhs parse");
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Instruction {
+    Mul(i64, i64),
+    Do,
+    Dont,
+}
+
+impl Solution for Day03 {
+    type Input = Vec<Instruction>;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        let mul = delimited(
+            tag("mul("),
+            separated_pair(
+                unsigned(),
+                delimited(spaces(), tag(","), spaces()),
+                unsigned(),
+            ),
+            tag(")"),
+        );
+        let instruction = alt((
+            map(mul, |(lhs, rhs)| Instruction::Mul(lhs, rhs)),
+            map(tag("do()"), |_| Instruction::Do),
+            map(tag("don't()"), |_| Instruction::Dont),
+        ));
 
-            lhs * rhs
-        })
-        .sum()
+        parse::complete(input, scan(instruction))
+    }
+
+    fn part_one(instructions: &Self::Input) -> Answer {
+        instructions
+            .iter()
+            .map(|i| match i {
+                Instruction::Mul(lhs, rhs) => lhs * rhs,
+                _ => 0,
+            })
+            .sum::<i64>()
+            .into()
+    }
+
+    fn part_two(instructions: &Self::Input) -> Answer {
+        instructions
+            .iter()
+            .fold((true, 0), |(active, sum), i| match i {
+                Instruction::Dont => (false, sum),
+                Instruction::Do => (true, sum),
+                Instruction::Mul(lhs, rhs) if active => (active, sum + lhs * rhs),
+                Instruction::Mul(..) => (active, sum),
+            })
+            .1
+            .into()
+    }
 }
 
-pub fn star_two(input: &str) -> i64 {
-    let re = Regex::new(r"(?:mul\((\d+)\s*,\s*(\d+)\)|do\(\)|don't\(\))").unwrap();
+pub fn star_one(input: &str) -> Answer {
+    star_one_reader(input.as_bytes()).expect("valid input")
+}
 
-    re.captures_iter(input)
-        .fold((true, 0), |(active, sum), c| {
-            let op = c.get(0).expect("operation");
-            if op.as_str().starts_with("don't") {
-                return (false, sum);
-            }
+pub fn star_two(input: &str) -> Answer {
+    star_two_reader(input.as_bytes()).expect("valid input")
+}
 
-            if op.as_str().starts_with("do") {
-                return (true, sum);
-            }
+/// [`star_one`] reading from `reader` one byte at a time.
+pub fn star_one_reader(reader: impl BufRead) -> Result<Answer, StreamError> {
+    let mut scanner = Scanner::default();
+    let mut sum = 0;
+    stream::for_each_byte(reader, |b| {
+        if let Some(Instruction::Mul(lhs, rhs)) = scanner.feed(b) {
+            sum += lhs * rhs;
+        }
+    })?;
 
-            if !active {
-                return (active, sum);
-            }
+    Ok(sum.into())
+}
+
+/// [`star_two`] reading from `reader` one byte at a time.
+pub fn star_two_reader(reader: impl BufRead) -> Result<Answer, StreamError> {
+    let mut scanner = Scanner::default();
+    let (mut active, mut sum) = (true, 0);
+    stream::for_each_byte(reader, |b| match scanner.feed(b) {
+        Some(Instruction::Do) => active = true,
+        Some(Instruction::Dont) => active = false,
+        Some(Instruction::Mul(lhs, rhs)) if active => sum += lhs * rhs,
+        _ => {}
+    })?;
+
+    Ok(sum.into())
+}
+
+/// Any amount of ASCII whitespace, which is all the [`Scanner`] can see one byte at a time.
+fn spaces<'a>() -> impl Parser<'a, &'a str> {
+    move |input: &'a str| {
+        let rest = input.trim_start_matches(|c: char| c.is_ascii() && is_space(c as u8));
+
+        Ok((&input[..input.len() - rest.len()], rest))
+    }
+}
+
+/// ASCII whitespace including the vertical tab, unlike [`u8::is_ascii_whitespace`].
+fn is_space(byte: u8) -> bool {
+    matches!(byte, b' ' | b'\t' | b'\n' | b'\x0b' | b'\x0c' | b'\r')
+}
+
+const LITERALS: [&[u8]; 3] = [b"mul(", b"do()", b"don't()"];
+
+/// Recognises the same instructions as [`Day03::parse`] one byte at a time, i.e. a state
+/// machine for `mul\((\d+)\s*,\s*(\d+)\)|do\(\)|don't\(\)` where `\s` is ASCII whitespace.
+#[derive(Debug, Default)]
+struct Scanner {
+    state: State,
+}
 
-            let lhs = c.get(1).expect("left capture");
-            let rhs = c.get(2).expect("right capture");
+#[derive(Debug, Clone, Copy)]
+enum State {
+    /// The first `matched` bytes of `LITERALS[literal]` have been seen.
+    Literal {
+        literal: usize,
+        matched: usize,
+    },
+    /// Within `mul(`, the left operand has these digits so far.
+    Lhs(Option<i64>),
+    /// Whitespace after the left operand.
+    Comma(i64),
+    /// After the comma, before the right operand.
+    AfterComma(i64),
+    Rhs(i64, i64),
+}
 
-            let lhs = lhs.as_str().parse::<i64>().expect("lhs parse");
-            let rhs = rhs.as_str().parse::<i64>().expect("rhs parse");
+impl Default for State {
+    fn default() -> Self {
+        Self::Literal {
+            literal: 0,
+            matched: 0,
+        }
+    }
+}
 
-            (active, sum + lhs * rhs)
-        })
-        .1
+impl Scanner {
+    /// Advance by `byte`, returning the instruction it completes.
+    fn feed(&mut self, byte: u8) -> Option<Instruction> {
+        let digit = || byte.is_ascii_digit().then(|| i64::from(byte - b'0'));
+        let push = |value: i64| value.checked_mul(10)?.checked_add(digit()?);
+        let space = is_space(byte);
+
+        let next = match self.state {
+            State::Literal { literal, matched } => {
+                let prefix = &LITERALS[literal][..matched];
+                let next = LITERALS
+                    .iter()
+                    .position(|l| l.len() > matched && l.starts_with(prefix) && l[matched] == byte);
+
+                match next {
+                    Some(0) if matched + 1 == LITERALS[0].len() => Some(State::Lhs(None)),
+                    Some(1) if matched + 1 == LITERALS[1].len() => {
+                        self.state = State::default();
+                        return Some(Instruction::Do);
+                    }
+                    Some(2) if matched + 1 == LITERALS[2].len() => {
+                        self.state = State::default();
+                        return Some(Instruction::Dont);
+                    }
+                    Some(literal) => Some(State::Literal {
+                        literal,
+                        matched: matched + 1,
+                    }),
+                    None => None,
+                }
+            }
+            State::Lhs(value) => match (value, byte) {
+                (_, b'0'..=b'9') => push(value.unwrap_or(0)).map(|v| State::Lhs(Some(v))),
+                (Some(lhs), b',') => Some(State::AfterComma(lhs)),
+                (Some(lhs), _) if space => Some(State::Comma(lhs)),
+                _ => None,
+            },
+            State::Comma(lhs) => match byte {
+                b',' => Some(State::AfterComma(lhs)),
+                _ if space => Some(State::Comma(lhs)),
+                _ => None,
+            },
+            State::AfterComma(lhs) => match digit() {
+                Some(rhs) => Some(State::Rhs(lhs, rhs)),
+                None if space => Some(State::AfterComma(lhs)),
+                None => None,
+            },
+            State::Rhs(lhs, rhs) => match byte {
+                b')' => {
+                    self.state = State::default();
+                    return Some(Instruction::Mul(lhs, rhs));
+                }
+                _ => push(rhs).map(|rhs| State::Rhs(lhs, rhs)),
+            },
+        };
+
+        match next {
+            Some(state) => {
+                self.state = state;
+                None
+            }
+            None if matches!(self.state, State::Literal { matched: 0, .. }) => None,
+            None => {
+                // The byte that broke the match could start the next one
+                self.state = State::default();
+                self.feed(byte)
+            }
+        }
+    }
 }
 
 #[cfg(test)]
 mod tests {
-    use super::{star_one, star_two};
-    const INPUT: &'static str = r#"
+    use super::{star_one, star_one_reader, star_two};
+    use super::{Day03, Instruction, Scanner};
+    use crate::{gen, prop, Solution};
+    const INPUT: &str = r#"
 xmul(2,4)%&mul[3,7]!@^do_not_mul(5,5)+mul(32,64]then(mul(11,8)mul(8,5))
     "#;
 
@@ -64,4 +235,84 @@ xmul(2,4)%&mul[3,7]!@^do_not_mul(5,5)+mul(32,64]then(mul(11,8)mul(8,5))
             48
         );
     }
+
+    #[test]
+    fn test_scanner() {
+        let input =
+            "mmul(1 ,\n2)do(don't()mul(99999999999999999999,1)mul(3,4 )dodo()mul(-1,2)mul(5,6)";
+        let mut scanner = Scanner::default();
+        let scanned: Vec<_> = input.bytes().filter_map(|b| scanner.feed(b)).collect();
+
+        assert_eq!(
+            scanned,
+            vec![
+                Instruction::Mul(1, 2),
+                Instruction::Dont,
+                Instruction::Do,
+                Instruction::Mul(5, 6)
+            ]
+        );
+        assert_eq!(Day03::parse(input).unwrap(), scanned);
+
+        // Only ASCII whitespace, in both grammars
+        for (input, expected) in [
+            ("mul(2,\x0b3)", 6),
+            ("mul(2,\u{a0}3)", 0),
+            ("mul(2\u{3000},3)", 0),
+        ] {
+            assert_eq!(star_one(input), expected, "{input:?}");
+            assert_eq!(
+                star_one_reader(input.as_bytes()).unwrap(),
+                expected,
+                "{input:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_scanner_matches_parse() {
+        // Generated memory, or fragments of instructions and whitespace thrown together
+        const PIECES: [&str; 9] = ["m", "mul(", "do", "do()", "don't()", "n't", "(", ")", "12"];
+        const SPACES: [&str; 7] = ["", " ", "\n", "\x0b", "\u{a0}", "\u{3000}", ","];
+        let memory = prop::from_fn(
+            |rng| {
+                if rng.chance(0.3) {
+                    let size = rng.range(1..=3) as usize;
+                    return gen::day03(rng, size).input;
+                }
+
+                let mut memory = String::new();
+                for _ in 0..rng.range(0..=20) {
+                    match rng.index(3) {
+                        0 => memory.push_str(PIECES[rng.index(PIECES.len())]),
+                        1 => memory.push_str(SPACES[rng.index(SPACES.len())]),
+                        _ => {
+                            let (lhs, rhs) = (rng.range(0..=99), rng.range(0..=99));
+                            let (before, after) = (*rng.choose(&SPACES), *rng.choose(&SPACES));
+                            memory.push_str(&format!("mul({lhs}{before},{after}{rhs})"));
+                        }
+                    }
+                }
+
+                memory
+            },
+            |memory: &String| {
+                memory
+                    .char_indices()
+                    .map(|(i, _)| {
+                        let mut shorter = memory.clone();
+                        shorter.remove(i);
+                        shorter
+                    })
+                    .collect()
+            },
+        );
+
+        prop::assert_holds(prop::Config::cases(500), &memory, |memory| {
+            let mut scanner = Scanner::default();
+            let scanned: Vec<_> = memory.bytes().filter_map(|b| scanner.feed(b)).collect();
+
+            Day03::parse(memory).unwrap() == scanned
+        });
+    }
 }
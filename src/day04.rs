@@ -1,104 +1,97 @@
 use std::collections::HashSet;
 
-use crate::math::Vector2;
+use crate::grid::Grid;
+use crate::math::{Direction, Vector2};
+use crate::{Answer, ParseError, Solution};
 
-pub fn star_one(input: &str) -> usize {
+pub struct Day04;
+
+impl Solution for Day04 {
+    type Input = Grid<char>;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        Grid::parse(input)
+    }
+
+    fn part_one(grid: &Self::Input) -> Answer {
+        count_xmas(grid).into()
+    }
+
+    fn part_two(grid: &Self::Input) -> Answer {
+        count_x_mas(grid).into()
+    }
+}
+
+pub fn star_one(input: &str) -> Answer {
+    Day04::part_one(&Day04::parse(input).expect("valid input"))
+}
+
+pub fn star_two(input: &str) -> Answer {
+    Day04::part_two(&Day04::parse(input).expect("valid input"))
+}
+
+fn count_xmas(grid: &Grid<char>) -> usize {
     const NEEDLE: [char; 4] = ['X', 'M', 'A', 'S'];
-    let grid: Vec<Vec<char>> = input
-        .lines()
-        .map(str::trim)
-        .filter(|l| !l.is_empty())
-        .map(|l| l.chars().collect())
-        .collect();
-
-    let mut covered: HashSet<((usize, usize), (isize, isize))> = HashSet::new();
-    let mut run = |pos, dir| -> usize {
-        if covered.contains(&(pos, dir)) {
+
+    let mut covered: HashSet<(Vector2<isize>, Direction)> = HashSet::new();
+    let mut run = |(x, y), dir| -> usize {
+        let pos = Vector2::new(x, y);
+        if !covered.insert((pos, dir)) {
             return 0;
         }
 
-        covered.insert((pos, dir));
-        let mut iter = GridIterator::new(&grid, (pos.0 as isize, pos.1 as isize), dir);
-        return iter.count_needles(&NEEDLE);
+        count_needles(grid.ray(pos, dir).map(|(_, c)| *c), &NEEDLE)
     };
 
-    let y_max = grid.len() - 1;
-    let x_max = grid[0].len() - 1;
+    let y_max = grid.height() as isize - 1;
+    let x_max = grid.width() as isize - 1;
     let mut count = 0;
-    for x in 0..grid[0].len() {
-        //Down
-        count += run((x, 0), (0, 1));
-        // Up
-        count += run((x, y_max), (0, -1));
-
-        // Down left
-        count += run((x, 0), (-1, 1));
-        // Down right
-        count += run((x, 0), (1, 1));
-
-        // Up left
-        count += run((x, y_max), (-1, -1));
-        //Up right
-        count += run((x, y_max), (1, -1));
+    for x in 0..=x_max {
+        count += run((x, 0), Direction::Down);
+        count += run((x, y_max), Direction::Up);
+        count += run((x, 0), Direction::DownLeft);
+        count += run((x, 0), Direction::DownRight);
+        count += run((x, y_max), Direction::UpLeft);
+        count += run((x, y_max), Direction::UpRight);
     }
 
-    for y in 0..grid.len() {
-        // Right
-        count += run((0, y), (1, 0));
-        // Left
-        count += run((x_max, y), (-1, 0));
-
-        // Down right
-        count += run((0, y), (1, 1));
-        // Up right
-        count += run((0, y), (1, -1));
-
-        //Up left
-        count += run((x_max, y), (-1, -1));
-
-        // Down left
-        count += run((x_max, y), (-1, 1));
+    for y in 0..=y_max {
+        count += run((0, y), Direction::Right);
+        count += run((x_max, y), Direction::Left);
+        count += run((0, y), Direction::DownRight);
+        count += run((0, y), Direction::UpRight);
+        count += run((x_max, y), Direction::UpLeft);
+        count += run((x_max, y), Direction::DownLeft);
     }
 
     count
 }
 
-pub fn star_two(input: &str) -> usize {
-    let grid: Vec<Vec<char>> = input
-        .lines()
-        .map(str::trim)
-        .filter(|l| !l.is_empty())
-        .map(|l| l.chars().collect())
-        .collect();
-
-    // All the 'A's
-    let needles = grid
-        .iter()
-        .enumerate()
-        .flat_map(|(y, row)| row.iter().enumerate().map(move |(x, c)| ((x, y), *c)))
-        .filter(|(_, c)| *c == 'A');
-
+fn count_x_mas(grid: &Grid<char>) -> usize {
     const NEEDLE: [char; 3] = ['M', 'A', 'S'];
     // Have As, check surrounding characters
     // M.S
     // .A.
     // M.S
-    needles
-        .filter(|((x, y), _)| {
-            let x = *x as isize;
-            let y = *y as isize;
-
+    grid.iter()
+        .filter(|(_, c)| **c == 'A')
+        .filter(|(pos, _)| {
             // 1.2
             // .A.
             // 4.3
             let count = [
-                ((x - 1, y - 1), (1, 1)),   // 1, down right
-                ((x + 1, y - 1), (-1, 1)),  // 2, down left
-                ((x + 1, y + 1), (-1, -1)), // 3, up left
-                ((x - 1, y + 1), (1, -1)),  // 4, up right
+                Direction::DownRight, // 1
+                Direction::DownLeft,  // 2
+                Direction::UpLeft,    // 3
+                Direction::UpRight,   // 4
             ]
-            .iter()
-            .filter(|(pos, dir)| GridIterator::new(&grid, *pos, *dir).find_needle(&NEEDLE))
+            .into_iter()
+            .filter(|dir| {
+                // Start one step behind the A
+                let ray = grid.ray(*pos - dir.offset(), *dir);
+
+                find_needle(ray.map(|(_, c)| *c), &NEEDLE)
+            })
             .count();
 
             count == 2
@@ -106,27 +99,9 @@ pub fn star_two(input: &str) -> usize {
         .count()
 }
 
-struct GridIterator<'a> {
-    grid: &'a [Vec<char>],
-    direction: Vector2<isize>,
-    current: Vector2<isize>,
-}
-
-impl GridIterator<'_> {
-    fn new(
-        grid: &[Vec<char>],
-        start: (isize, isize),
-        direction: (isize, isize),
-    ) -> GridIterator<'_> {
-        GridIterator {
-            grid,
-            direction: Vector2::new(direction.0, direction.1),
-            current: Vector2::new(start.0, start.1),
-        }
-    }
-
-    fn count_needles(&mut self, needle: &[char]) -> usize {
-        self.fold((0, 0), |(count, nidx), c| {
+fn count_needles(cells: impl Iterator<Item = char>, needle: &[char]) -> usize {
+    cells
+        .fold((0, 0), |(count, nidx), c| {
             if c != needle[nidx] {
                 if c != needle[0] {
                     return (count, 0);
@@ -144,51 +119,29 @@ impl GridIterator<'_> {
             (count + found, nidx)
         })
         .0
-    }
-
-    fn find_needle(&mut self, needle: &[char]) -> bool {
-        // NB: cannot use `Iterator::all` because self might be shorter than needle which results
-        // in false positive.
-        let res = self.take(needle.len()).zip(needle.iter()).try_fold(
-            (true, needle.len()),
-            |(valid, remain), (c, n)| {
-                if !valid {
-                    return None;
-                }
-
-                Some((valid && c == *n, remain - 1))
-            },
-        );
-
-        res.map(|(valid, i)| valid && i == 0).unwrap_or(false)
-    }
 }
 
-impl Iterator for GridIterator<'_> {
-    type Item = char;
-
-    fn next(&mut self) -> Option<Self::Item> {
-        if self.current.y < 0 || self.current.y as usize >= self.grid.len() {
-            return None;
-        }
-
-        if self.current.x < 0 || self.current.x as usize >= self.grid[self.current.y as usize].len()
-        {
-            return None;
-        }
+fn find_needle(cells: impl Iterator<Item = char>, needle: &[char]) -> bool {
+    // NB: cannot use `Iterator::all` because the cells might be shorter than needle which results
+    // in false positive.
+    let res = cells.take(needle.len()).zip(needle.iter()).try_fold(
+        (true, needle.len()),
+        |(valid, remain), (c, n)| {
+            if !valid {
+                return None;
+            }
 
-        let next = self.current + self.direction;
-        let c = self.grid[self.current.y as usize][self.current.x as usize];
-        self.current = next;
+            Some((valid && c == *n, remain - 1))
+        },
+    );
 
-        Some(c)
-    }
+    res.map(|(valid, i)| valid && i == 0).unwrap_or(false)
 }
 
 #[cfg(test)]
 mod tests {
     use super::{star_one, star_two};
-    const INPUT: &'static str = r#"
+    const INPUT: &str = r#"
 ....XXMAS.
 .SAMXMS...
 ...S..A...
@@ -201,7 +154,7 @@ S.S.S.S.SS
 .X.X.XMASX
 "#;
 
-    const INPUT2: &'static str = r#"
+    const INPUT2: &str = r#"
 .M.S......
 ..A..MSMS.
 .M.S.MAA..
@@ -1,42 +1,63 @@
 use std::cmp::Ordering;
 use std::collections::{HashMap, HashSet};
 
-type Rules = HashMap<usize, HashSet<usize>>;
+use crate::parse::{self, context, lines, pair, section, separated, separated_pair, tag, unsigned};
+use crate::{Answer, ParseError, Solution};
 
-pub fn star_one(input: &str) -> usize {
-    let (rules, updates) = parse(input);
+pub type Rules = HashMap<usize, HashSet<usize>>;
 
-    // For all updates
-    updates
-        .filter(|update| validate(&rules, update))
-        .map(|update| update[update.len() / 2])
-        .sum()
-}
+pub struct Day05;
 
-pub fn star_two(input: &str) -> usize {
-    let (rules, updates) = parse(input);
+impl Solution for Day05 {
+    type Input = (Rules, Vec<Vec<usize>>);
 
-    let bad = updates.filter(|update| !validate(&rules, update));
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse(input)
+    }
 
-    bad.map(|mut update| {
-        update.sort_by(|a, b| {
-            let a_before_b = rules.get(a).map(|o| o.contains(b)).unwrap_or(false);
-            if a_before_b {
-                return Ordering::Greater;
-            }
+    fn part_one((rules, updates): &Self::Input) -> Answer {
+        // For all updates
+        updates
+            .iter()
+            .filter(|update| validate(rules, update))
+            .map(|update| update[update.len() / 2])
+            .sum::<usize>()
+            .into()
+    }
 
-            let b_before_a = rules.get(b).map(|o| o.contains(a)).unwrap_or(false);
+    fn part_two((rules, updates): &Self::Input) -> Answer {
+        let bad = updates.iter().filter(|update| !validate(rules, update));
 
-            if b_before_a {
-                return Ordering::Less;
-            }
+        bad.map(|update| {
+            let mut update = update.clone();
+            update.sort_by(|a, b| {
+                let a_before_b = rules.get(a).map(|o| o.contains(b)).unwrap_or(false);
+                if a_before_b {
+                    return Ordering::Greater;
+                }
 
-            unreachable!("Should have total order")
-        });
+                let b_before_a = rules.get(b).map(|o| o.contains(a)).unwrap_or(false);
 
-        update[update.len() / 2]
-    })
-    .sum()
+                if b_before_a {
+                    return Ordering::Less;
+                }
+
+                unreachable!("Should have total order")
+            });
+
+            update[update.len() / 2]
+        })
+        .sum::<usize>()
+        .into()
+    }
+}
+
+pub fn star_one(input: &str) -> Answer {
+    Day05::part_one(&Day05::parse(input).expect("valid input"))
+}
+
+pub fn star_two(input: &str) -> Answer {
+    Day05::part_two(&Day05::parse(input).expect("valid input"))
 }
 
 /// Validate the update according to the rules.
@@ -75,48 +96,28 @@ fn validate(rules: &Rules, update: &[usize]) -> bool {
     })
 }
 
-fn parse(input: &str) -> (Rules, impl Iterator<Item = Vec<usize>> + '_) {
-    let (rules, updates) = input
-        .split_once("\n\n")
-        .expect("Two sections separated by \\n\\n");
-    let rules = {
-        let mut result: Rules = Default::default();
-        let iter = rules
-            .lines()
-            .map(str::trim)
-            .filter(|l| !l.is_empty())
-            .map(|l| {
-                let (key, value) = l.split_once('|').expect("rule separated by |");
-                let key = key.parse().expect("parsable key");
-                let value = value.parse().expect("parsable key");
-
-                (key, value)
-            });
+fn parse(input: &str) -> Result<(Rules, Vec<Vec<usize>>), ParseError> {
+    let rule = context(
+        "rule separated by `|`",
+        separated_pair(unsigned(), tag("|"), unsigned()),
+    );
+    let update = separated(unsigned(), tag(","));
 
-        for (key, value) in iter {
-            result.entry(key).or_default().insert(value);
-        }
+    let (rule_list, updates) =
+        parse::complete(input, pair(section(lines(rule)), section(lines(update))))?;
 
-        result
-    };
-
-    let updates = updates
-        .lines()
-        .map(str::trim)
-        .filter(|l| !l.is_empty())
-        .map(|l| {
-            l.split(',')
-                .map(|s| s.parse::<usize>().expect("parsable page number"))
-                .collect::<Vec<_>>()
-        });
+    let mut rules: Rules = Default::default();
+    for (key, value) in rule_list {
+        rules.entry(key).or_default().insert(value);
+    }
 
-    (rules, updates)
+    Ok((rules, updates))
 }
 
 #[cfg(test)]
 mod tests {
     use super::{star_one, star_two};
-    const INPUT: &'static str = r#"
+    const INPUT: &str = r#"
 47|53
 97|13
 97|61
@@ -1,124 +1,198 @@
 use std::collections::HashSet;
+use std::io;
+use std::iter;
+use std::path::{Path, PathBuf};
 
-use crate::math::Vector2;
+use crate::grid::Grid;
+use crate::math::{Direction, Vector2};
+use crate::parallel::Strategy;
+use crate::viz::{Color, Recorder, Style};
+use crate::{cycle, Answer, ParseError, Solution};
 
-pub fn star_one(input: &str) -> usize {
-    let mut grid = Grid::from(input);
+pub struct Day06;
 
-    grid.run_until_stuck_or_out_of_bounds()
-        .expect("out of bounds")
-        .len()
+impl Solution for Day06 {
+    type Input = Lab;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        Lab::parse(input)
+    }
+
+    fn part_one(lab: &Self::Input) -> Answer {
+        lab.patrol().expect("out of bounds").len().into()
+    }
+
+    fn part_two(lab: &Self::Input) -> Answer {
+        count_loops(lab, Strategy::default()).into()
+    }
 }
 
-pub fn star_two(input: &str) -> usize {
-    let grid = Grid::from(input);
-    let visited = {
-        let mut grid = grid.clone();
-
-        grid.run_until_stuck_or_out_of_bounds()
-            .expect("out of bounds")
-    };
-
-    // Brute force is good enough, try every location visited
-    visited
-        .into_iter()
-        .filter(|x| {
-            let mut grid = grid.clone();
-            // Add obstruction
-            if !grid.obstruct(*x) {
-                return false;
-            }
+/// The number of locations where a new obstruction makes the guard loop.
+fn count_loops(lab: &Lab, strategy: Strategy) -> usize {
+    let path = lab.path().expect("out of bounds");
+
+    // Only locations on the path matter and nothing changes until the guard first reaches the new
+    // obstruction, so each check starts from the step before instead of the guard's start
+    let mut seen = HashSet::from([lab.guard.location]);
+    let candidates: Vec<_> = path
+        .windows(2)
+        .filter(|w| seen.insert(w[1].location))
+        .map(|w| (w[1].location, w[0]))
+        .collect();
+
+    strategy.count(&candidates, |(at, before)| {
+        cycle::brent(*before, |g| lab.step_around(g, Some(*at))).is_some()
+    })
+}
 
-            grid.run_until_stuck_or_out_of_bounds().is_none()
-        })
-        .count()
+pub fn star_one(input: &str) -> Answer {
+    Day06::part_one(&Day06::parse(input).expect("valid input"))
 }
 
-#[derive(Clone)]
-struct Grid {
-    grid: Vec<Vec<Location>>,
-    guard_location: Option<Vector2<isize>>,
+pub fn star_two(input: &str) -> Answer {
+    Day06::part_two(&Day06::parse(input).expect("valid input"))
 }
 
-impl Grid {
-    fn run_until_stuck_or_out_of_bounds(&mut self) -> Option<HashSet<Vector2<isize>>> {
-        let mut locations = HashSet::new();
-        while let Some(guard_location) = self.guard_location() {
-            if !locations.insert((guard_location, self.guard().direction)) {
-                return None;
-            }
+#[derive(Clone)]
+pub struct Lab {
+    grid: Grid<Location>,
+    guard: Guard,
+}
 
-            self.tick();
-        }
+impl Lab {
+    fn parse(input: &str) -> Result<Self, ParseError> {
+        let mut grid = Grid::parse(input)?;
+        let location = grid
+            .find(Location::is_guard)
+            .ok_or_else(|| ParseError::at(input, input.trim(), "a guard `^`"))?;
+        let Location::Guard(direction) = grid[location] else {
+            unreachable!("Found guard at {location:?}");
+        };
+        grid[location] = Location::Empty;
 
-        locations
-            .into_iter()
-            .map(|(l, _)| l)
-            .collect::<HashSet<_>>()
-            .into()
+        Ok(Self {
+            grid,
+            guard: Guard {
+                location,
+                direction,
+            },
+        })
     }
-    fn guard_location(&self) -> Option<Vector2<isize>> {
-        self.guard_location
+
+    /// The locations visited by the guard before they leave, `None` if they get stuck in a loop.
+    pub fn patrol(&self) -> Option<HashSet<Vector2<isize>>> {
+        self.path()
+            .map(|path| path.into_iter().map(|g| g.location).collect())
     }
 
-    fn obstruct(&mut self, at: Vector2<isize>) -> bool {
-        // Cannot obstruct guard
-        if at == self.guard_location.expect("guard") {
-            return false;
+    /// Every state of the guard until they leave, `None` if they get stuck in a loop.
+    fn path(&self) -> Option<Vec<Guard>> {
+        if self.is_loop() {
+            return None;
         }
 
-        self.grid[at.y as usize][at.x as usize] = Location::Obstruction;
-        true
+        iter::successors(Some(self.guard), |g| self.step(g))
+            .collect::<Vec<_>>()
+            .into()
     }
 
-    fn tick(&mut self) {
-        let Some(guard_location) = self.guard_location else {
-            return;
-        };
-        let guard_direction = self.guard().direction;
-        let next = guard_location + guard_direction;
+    fn is_loop(&self) -> bool {
+        cycle::brent(self.guard, |g| self.step(g)).is_some()
+    }
 
-        if self.out_of_bounds(next) {
-            self.guard_location = None;
-            return;
-        }
+    /// The guard's next state, `None` once they leave the lab.
+    fn step(&self, guard: &Guard) -> Option<Guard> {
+        self.step_around(guard, None)
+    }
 
-        if self.grid[next.y as usize][next.x as usize] == Location::Obstruction {
-            self.guard_mut().direction = guard_direction.rotate_right();
-            return;
+    /// [`Lab::step`] with an optional `extra` obstruction that isn't part of the grid.
+    fn step_around(&self, guard: &Guard, extra: Option<Vector2<isize>>) -> Option<Guard> {
+        let next = guard.location + guard.direction.offset();
+        let location = self.grid.get(next)?;
+
+        if *location == Location::Obstruction || extra == Some(next) {
+            Some(Guard {
+                direction: guard.direction.rotate_right(),
+                ..*guard
+            })
+        } else {
+            Some(Guard {
+                location: next,
+                ..*guard
+            })
         }
+    }
 
-        self.grid[guard_location.y as usize][guard_location.x as usize] = Location::Empty;
-        self.guard_location = Some(next);
-        self.grid[next.y as usize][next.x as usize] = Location::Guard(Guard {
-            direction: guard_direction,
-        });
+    /// Whether `at` is inside the lab.
+    pub fn contains(&self, at: Vector2<isize>) -> bool {
+        self.grid.get(at).is_some()
     }
 
-    fn out_of_bounds(&self, next: Vector2<isize>) -> bool {
-        next.x < 0
-            || next.y < 0
-            || next.y as usize >= self.grid.len()
-            || next.x as usize >= self.grid[0].len()
+    /// Write the frames of [`Lab::record_patrol`] to `dir` as `day06-0000.ppm`, ... with every
+    /// cell drawn as a `scale` by `scale` square. Returns the written paths.
+    ///
+    /// **Note:** Panics if `candidate` is outside the lab, `interval` or `scale` is zero
+    pub fn write_patrol(
+        &self,
+        dir: &Path,
+        candidate: Option<Vector2<isize>>,
+        interval: usize,
+        scale: usize,
+    ) -> io::Result<Vec<PathBuf>> {
+        self.record_patrol(candidate, interval)
+            .write_ppm(dir, "day06", scale, |_, t| t.color())
     }
 
-    fn guard(&self) -> &Guard {
-        let guard_location = self.guard_location.expect("guard");
-        &self.grid[guard_location.y as usize][guard_location.x as usize]
-            .as_guard()
-            .unwrap_or_else(|| {
-                panic!(
-                    "Expected guard at {guard_location:?} found {:?}",
-                    self.grid[guard_location.y as usize][guard_location.x as usize]
-                )
-            })
+    /// Record the guard's patrol with an optional `candidate` obstruction, keeping every
+    /// `interval`th step and the last one. Stops when the guard leaves or starts repeating.
+    ///
+    /// **Note:** Panics if `candidate` is outside the lab or `interval` is zero
+    pub fn record_patrol(
+        &self,
+        candidate: Option<Vector2<isize>>,
+        interval: usize,
+    ) -> Recorder<Tile> {
+        let mut lab = self.clone();
+        let mut tiles = self.grid.map(|l| match l {
+            Location::Obstruction => Tile::Obstruction,
+            _ => Tile::Empty,
+        });
+        if let Some(candidate) = candidate.filter(|c| lab.obstruct(*c)) {
+            tiles[candidate] = Tile::Candidate;
+        }
+
+        let mut recorder = Recorder::every(interval);
+        let mut seen = HashSet::from([lab.guard]);
+        let mut guard = lab.guard;
+        loop {
+            tiles[guard.location] = Tile::Visited;
+            let frame = || {
+                let mut frame = tiles.clone();
+                frame[guard.location] = Tile::Guard(guard.direction);
+                frame
+            };
+
+            match lab.step(&guard).filter(|next| seen.insert(*next)) {
+                Some(next) => {
+                    recorder.record(frame);
+                    guard = next;
+                }
+                None => {
+                    recorder.finish(frame);
+                    return recorder;
+                }
+            }
+        }
     }
 
-    fn guard_mut(&mut self) -> &mut Guard {
-        let guard_location = self.guard_location.expect("guard");
-        self.grid[guard_location.y as usize][guard_location.x as usize]
-            .as_guard_mut()
-            .unwrap()
+    fn obstruct(&mut self, at: Vector2<isize>) -> bool {
+        // Cannot obstruct guard
+        if at == self.guard.location {
+            return false;
+        }
+
+        self.grid[at] = Location::Obstruction;
+        true
     }
 }
 
@@ -126,102 +200,89 @@ impl Grid {
 enum Location {
     Empty,
     Obstruction,
-    Guard(Guard),
+    /// Only present while parsing, the guard moves independently of the grid.
+    Guard(Direction),
 }
 
+/// A cell of a recorded patrol, see [`Lab::record_patrol`].
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
-struct Guard {
-    direction: Vector2<isize>,
+pub enum Tile {
+    Empty,
+    Obstruction,
+    /// The obstruction added to make the guard loop.
+    Candidate,
+    Visited,
+    Guard(Direction),
 }
 
-impl Location {
-    fn is_guard(&self) -> bool {
-        matches!(self, Self::Guard { .. })
-    }
-
-    fn as_guard(&self) -> Option<&Guard> {
+impl Tile {
+    pub fn style(&self) -> Style {
         match self {
-            Self::Guard(g) => Some(g),
-            _ => None,
+            Self::Empty => Style::new('.'),
+            Self::Obstruction => Style::new('#').fg(Color::GRAY),
+            Self::Candidate => Style::new('O').fg(Color::RED),
+            Self::Visited => Style::new('X').fg(Color::BLUE),
+            Self::Guard(direction) => {
+                let glyph = match direction {
+                    Direction::Up => '^',
+                    Direction::Right => '>',
+                    Direction::Down => 'v',
+                    Direction::Left => '<',
+                    _ => '*',
+                };
+
+                Style::new(glyph).fg(Color::YELLOW)
+            }
         }
     }
 
-    fn as_guard_mut(&mut self) -> Option<&mut Guard> {
+    pub fn color(&self) -> Color {
         match self {
-            Self::Guard(g) => Some(g),
-            _ => None,
+            Self::Empty => Color::BLACK,
+            Self::Obstruction => Color::GRAY,
+            Self::Candidate => Color::RED,
+            Self::Visited => Color::BLUE,
+            Self::Guard(_) => Color::YELLOW,
         }
     }
 }
 
-impl From<&str> for Grid {
-    fn from(input: &str) -> Self {
-        let grid: Vec<Vec<Location>> = input
-            .lines()
-            .map(str::trim)
-            .filter(|l| !l.is_empty())
-            .map(|l| l.chars().map(Into::into).collect())
-            .collect();
-        let guard_location = grid
-            .iter()
-            .enumerate()
-            .find_map(|(y, row)| {
-                row.iter()
-                    .enumerate()
-                    .find_map(|(x, l)| l.is_guard().then(|| Vector2::new(x as isize, y as isize)))
-            })
-            .expect("guard");
-
-        Self {
-            grid,
-            guard_location: Some(guard_location),
-        }
-    }
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+struct Guard {
+    location: Vector2<isize>,
+    direction: Direction,
 }
 
-impl From<char> for Location {
-    fn from(value: char) -> Self {
-        match value {
-            '.' => Self::Empty,
-            '#' => Self::Obstruction,
-            '^' => Self::Guard(Guard {
-                direction: Vector2::new(0, -1),
-            }),
-            _ => unreachable!("Invalid location {value}"),
-        }
+impl Location {
+    fn is_guard(&self) -> bool {
+        matches!(self, Self::Guard { .. })
     }
 }
 
-trait Vector2Ext {
-    const UP: Self;
-    const DOWN: Self;
-    const LEFT: Self;
-    const RIGHT: Self;
-
-    fn rotate_right(&self) -> Self;
-}
+impl TryFrom<char> for Location {
+    type Error = char;
 
-impl Vector2Ext for Vector2<isize> {
-    const UP: Self = Vector2::new(0, -1);
-    const DOWN: Self = Vector2::new(0, 1);
-    const LEFT: Self = Vector2::new(-1, 0);
-    const RIGHT: Self = Vector2::new(1, 0);
-
-    fn rotate_right(&self) -> Self {
-        match *self {
-            Self::UP => Self::RIGHT,
-            Self::RIGHT => Self::DOWN,
-            Self::DOWN => Self::LEFT,
-            Self::LEFT => Self::UP,
-            _ => unreachable!(),
+    fn try_from(value: char) -> Result<Self, Self::Error> {
+        match value {
+            '.' => Ok(Self::Empty),
+            '#' => Ok(Self::Obstruction),
+            _ => Direction::try_from(value).map(Self::Guard),
         }
     }
 }
 
 #[cfg(test)]
 mod tests {
-    use super::{star_one, star_two};
-    const INPUT: &'static str = r#"
+    use std::collections::HashSet;
+    use std::fs;
+
+    use super::{count_loops, star_one, star_two, Day06, Guard, Lab, Location, Tile};
+    use crate::math::Vector2;
+    use crate::parallel::Strategy;
+    use crate::prop::{self, from_fn, Config};
+    use crate::viz::Color;
+    use crate::{viz, ParseError, Solution};
+    const INPUT: &str = r#"
 ....#.....
 .........#
 ..........
@@ -243,4 +304,150 @@ mod tests {
     fn test_star_two() {
         assert_eq!(star_two(INPUT), 6);
     }
+
+    #[test]
+    fn test_strategies() {
+        let lab = Day06::parse(INPUT).unwrap();
+
+        assert_eq!(count_loops(&lab, Strategy::Serial), 6);
+        assert_eq!(count_loops(&lab, Strategy::Threads), 6);
+    }
+
+    #[test]
+    fn test_parse_error() {
+        assert_eq!(
+            Day06::parse("..#\n.^x")
+                .err()
+                .map(|e| (e.line, e.column, e.text)),
+            Some((2, 3, "x".to_owned()))
+        );
+        assert_eq!(
+            Day06::parse("..#\n...").err(),
+            Some(ParseError::new(1, 1, "..#\n...", "a guard `^`"))
+        );
+    }
+
+    #[test]
+    fn test_record_patrol() {
+        let lab = Day06::parse(INPUT).unwrap();
+
+        let patrol = lab.record_patrol(None, 1);
+        let last = patrol.frames().last().unwrap();
+        let visited = last
+            .iter()
+            .filter(|(_, t)| matches!(t, Tile::Visited | Tile::Guard(_)))
+            .count();
+        assert_eq!(visited, 41);
+        assert_eq!(
+            lab.record_patrol(None, 10).len(),
+            (patrol.len() - 1).div_ceil(10) + 1
+        );
+
+        let looping = lab.record_patrol(Some(Vector2::new(3, 6)), 1);
+        let first = viz::ansi(&looping.frames()[0], |_, t| t.style());
+        assert!(first.contains("\x1b[38;2;220;50;47mO"));
+        assert_eq!(
+            looping.frames().last().unwrap()[Vector2::new(3, 6)],
+            Tile::Candidate
+        );
+    }
+
+    #[test]
+    fn test_write_patrol() {
+        let lab = Day06::parse(INPUT).unwrap();
+        let dir = std::env::temp_dir().join(format!("aoc-day06-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+
+        let paths = lab
+            .write_patrol(&dir, Some(Vector2::new(3, 6)), 5, 1)
+            .unwrap();
+        assert_eq!(paths[0].file_name().unwrap(), "day06-0000.ppm");
+
+        // The first frame has the guard at (4, 6) right next to the candidate at (3, 6)
+        let image = fs::read(&paths[0]).unwrap();
+        let header = "P6\n10 10\n255\n".len();
+        let pixel = |x: usize, y: usize| {
+            let i = header + 3 * (y * 10 + x);
+            Color::new(image[i], image[i + 1], image[i + 2])
+        };
+        assert_eq!(pixel(4, 6), Color::YELLOW);
+        assert_eq!(pixel(3, 6), Color::RED);
+        assert_eq!(pixel(4, 0), Color::GRAY);
+        assert_eq!(pixel(0, 0), Color::BLACK);
+
+        assert!(lab.contains(Vector2::new(9, 9)));
+        assert!(!lab.contains(Vector2::new(10, 0)));
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    /// The states of the guard before they leave, `None` once one repeats. Unlike
+    /// [`Lab::patrol`] this keeps every state instead of detecting cycles.
+    fn walk_naive(lab: &Lab) -> Option<HashSet<Guard>> {
+        let mut seen = HashSet::from([lab.guard]);
+        let mut guard = lab.guard;
+        while let Some(next) = lab.step(&guard) {
+            if !seen.insert(next) {
+                return None;
+            }
+            guard = next;
+        }
+
+        Some(seen)
+    }
+
+    /// Reference for [`count_loops`], walking the whole patrol again on a copy of the lab for
+    /// every empty cell.
+    fn count_loops_naive(lab: &Lab) -> usize {
+        lab.grid
+            .positions()
+            .filter(|x| {
+                let mut lab = lab.clone();
+                lab.grid[*x] == Location::Empty && lab.obstruct(*x) && walk_naive(&lab).is_none()
+            })
+            .count()
+    }
+
+    #[test]
+    fn test_count_loops_matches_naive() {
+        // Small, crowded labs so that plenty of them have loops, shrunk by clearing obstructions
+        let labs = from_fn(
+            |rng| {
+                let (width, height) = (rng.range(1..=10) as usize, rng.range(1..=10) as usize);
+                let guard = rng.index(width * height);
+                let mut input = String::new();
+                for i in 0..width * height {
+                    input.push(if i == guard {
+                        *rng.choose(&['^', '>', 'v', '<'])
+                    } else if rng.chance(0.25) {
+                        '#'
+                    } else {
+                        '.'
+                    });
+                    if (i + 1).is_multiple_of(width) {
+                        input.push('\n');
+                    }
+                }
+
+                input
+            },
+            |input: &String| {
+                input
+                    .match_indices('#')
+                    .map(|(i, _)| format!("{}.{}", &input[..i], &input[i + 1..]))
+                    .collect()
+            },
+        );
+
+        prop::assert_holds(Config::cases(2000), &labs, |input| {
+            let lab = Day06::parse(input).unwrap();
+            // Only labs the guard leaves have an answer
+            let Some(states) = walk_naive(&lab) else {
+                return lab.patrol().is_none();
+            };
+            let visited: HashSet<_> = states.iter().map(|g| g.location).collect();
+
+            lab.patrol() == Some(visited)
+                && count_loops(&lab, Strategy::Serial) == count_loops_naive(&lab)
+        });
+    }
 }
@@ -1,72 +1,106 @@
-use itertools::{repeat_n, Itertools};
-
-pub fn star_one(input: &str) -> usize {
-    let cases = input
-        .lines()
-        .map(str::trim)
-        .filter(|l| !l.is_empty())
-        .map(Case::from);
-
-    // Worst case complexity is O(m * 2^(n-1)) where m is the number of cases and n is the number of values
-    // Longest input is 12 values for a total 2^11 = 2048 possible combinations
-    // For m of 850 cases, this is 1.7 million, brute force is fine
-
-    cases
-        .filter(|c| {
-            repeat_n([Op::Add, Op::Mul].into_iter(), c.values.len() - 1)
-                .multi_cartesian_product()
-                .any(|ops| c.is_correct(ops.into_iter()))
-        })
-        .map(|c| c.expected)
-        .sum()
+use std::io::BufRead;
+
+use crate::parallel::Strategy;
+use crate::parse::{
+    self, context, integer, lines, map, separated, separated_pair, spaces1, tag, Parser,
+};
+use crate::stream::{self, StreamError};
+use crate::{digits, Answer, ParseError, Solution};
+
+pub struct Day07;
+
+impl Solution for Day07 {
+    type Input = Vec<Case>;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse::complete(input, lines(Case::parser()))
+    }
+
+    fn part_one(cases: &Self::Input) -> Answer {
+        calibration(cases, &[Op::Add, Op::Mul], Strategy::default()).into()
+    }
+
+    fn part_two(cases: &Self::Input) -> Answer {
+        calibration(cases, &[Op::Add, Op::Mul, Op::Concat], Strategy::default()).into()
+    }
 }
 
-pub fn star_two(input: &str) -> usize {
-    let cases = input
-        .lines()
-        .map(str::trim)
-        .filter(|l| !l.is_empty())
-        .map(Case::from);
-
-    // Worst case complexity is O(m * 3^(n-1)) where m is the number of cases and n is the number of values
-    // Longest input is 12 values for a total 3^11 = 177147 possible combinations
-    // For m of 850 cases, this is 150 million, brute force is fine
-
-    cases
-        .filter(|c| {
-            repeat_n(
-                [Op::Add, Op::Mul, Op::Concat].into_iter(),
-                c.values.len() - 1,
-            )
-            .multi_cartesian_product()
-            .any(|ops| c.is_correct(ops.into_iter()))
-        })
-        .map(|c| dbg!(c.expected))
+/// The sum of the expected values of the cases that some combination of `ops` satisfies.
+fn calibration(cases: &[Case], ops: &[Op], strategy: Strategy) -> usize {
+    strategy
+        .map(cases, |c| if c.is_solvable(ops) { c.expected } else { 0 })
+        .into_iter()
         .sum()
 }
 
+pub fn star_one(input: &str) -> Answer {
+    star_one_reader(input.as_bytes()).expect("valid input")
+}
+
+pub fn star_two(input: &str) -> Answer {
+    star_two_reader(input.as_bytes()).expect("valid input")
+}
+
+/// [`star_one`] reading from `reader` one case at a time.
+pub fn star_one_reader(reader: impl BufRead) -> Result<Answer, StreamError> {
+    stream_calibration(reader, &[Op::Add, Op::Mul])
+}
+
+/// [`star_two`] reading from `reader` one case at a time.
+pub fn star_two_reader(reader: impl BufRead) -> Result<Answer, StreamError> {
+    stream_calibration(reader, &[Op::Add, Op::Mul, Op::Concat])
+}
+
+fn stream_calibration(reader: impl BufRead, ops: &[Op]) -> Result<Answer, StreamError> {
+    let mut sum = 0;
+    stream::for_each_line(reader, |line| {
+        let case = parse::complete(line, Case::parser())?;
+        if case.is_solvable(ops) {
+            sum += case.expected;
+        }
+
+        Ok(())
+    })?;
+
+    Ok(sum.into())
+}
+
 #[derive(Debug)]
-struct Case {
+pub struct Case {
     expected: usize,
     values: Vec<usize>,
 }
 
 impl Case {
-    fn is_correct(&self, ops: impl Iterator<Item = Op>) -> bool {
-        self.eval(ops) == self.expected
+    /// Whether some combination of `ops` produces the expected value.
+    fn is_solvable(&self, ops: &[Op]) -> bool {
+        solvable_from(self.expected, &self.values, ops)
     }
+}
 
-    fn eval(&self, ops: impl Iterator<Item = Op>) -> usize {
-        self.values
-            .iter()
-            .skip(1)
-            .zip(ops)
-            .fold(self.values[0], |acc, (v, op)| match op {
-                Op::Add => acc + v,
-                Op::Mul => acc * v,
-                Op::Concat => acc * 10_usize.pow(v.ilog10() + 1) + v,
-            })
+/// Whether `values` can produce `target`, undoing the operator applied to the last value at each
+/// step. Only operators that could have produced `target` are explored further.
+///
+/// The worst case is still every combination of `ops`, but pruning drops almost all of them early.
+fn solvable_from(target: usize, values: &[usize], ops: &[Op]) -> bool {
+    let Some((&last, rest)) = values.split_last() else {
+        return false;
+    };
+    if rest.is_empty() {
+        return target == last;
     }
+
+    ops.iter().any(|op| match op {
+        Op::Add => target
+            .checked_sub(last)
+            .is_some_and(|t| solvable_from(t, rest, ops)),
+        // Anything times zero is zero
+        Op::Mul if last == 0 => target == 0,
+        Op::Mul => target.is_multiple_of(last) && solvable_from(target / last, rest, ops),
+        Op::Concat => {
+            digits::strip_suffix(target, last).is_some_and(|t| solvable_from(t, rest, ops))
+        }
+    })
 }
 
 #[derive(Debug, Copy, Clone)]
@@ -76,31 +110,29 @@ enum Op {
     Concat,
 }
 
-impl From<&str> for Case {
-    fn from(value: &str) -> Self {
-        let mut parts = value.split(": ");
-        let expected = parts
-            .next()
-            .expect("two parts separated by :")
-            .parse()
-            .expect("parse expected");
-        let values = parts
-            .next()
-            .unwrap()
-            .split_whitespace()
-            .map(|v| v.parse().expect("parse value"))
-            .collect();
-
-        Self { expected, values }
+impl Case {
+    /// A single case, e.g. `190: 10 19`.
+    fn parser<'a>() -> impl Parser<'a, Self> {
+        let case = separated_pair(integer(), tag(": "), separated(integer(), spaces1()));
+
+        map(
+            context("two parts separated by `: `", case),
+            |(expected, values)| Self { expected, values },
+        )
     }
 }
 
 #[cfg(test)]
 mod tests {
+    use itertools::{repeat_n, Itertools};
+
     use crate::day07::Op;
 
-    use super::{star_one, star_two, Case};
-    const INPUT: &'static str = r#"
+    use super::{calibration, star_one, star_two, star_two_reader, Case, Day07};
+    use crate::parallel::Strategy;
+    use crate::prop::{self, int, vec, Config};
+    use crate::{digits, ParseError, Solution};
+    const INPUT: &str = r#"
 190: 10 19
 3267: 81 40 27
 83: 17 5
@@ -122,6 +154,16 @@ mod tests {
         assert_eq!(star_two(INPUT), 11387);
     }
 
+    #[test]
+    fn test_strategies() {
+        let cases = Day07::parse(INPUT).unwrap();
+        let ops = [Op::Add, Op::Mul, Op::Concat];
+
+        assert_eq!(calibration(&cases, &ops, Strategy::Serial), 11387);
+        assert_eq!(calibration(&cases, &ops, Strategy::Threads), 11387);
+        assert_eq!(star_two_reader(INPUT.as_bytes()).unwrap(), 11387);
+    }
+
     #[test]
     fn test_is_correct() {
         let case = Case {
@@ -166,4 +208,73 @@ mod tests {
 
         assert!(case.is_correct([Op::Mul, Op::Concat, Op::Mul].iter().copied()));
     }
+
+    #[test]
+    fn test_parse_error() {
+        assert_eq!(
+            Day07::parse("190: 10 19\n3267 81 40 27").err(),
+            Some(ParseError::new(
+                2,
+                1,
+                "3267 81 40 27",
+                "two parts separated by `: `"
+            ))
+        );
+        assert_eq!(
+            Day07::parse("190: 10 1x9").err(),
+            Some(ParseError::new(1, 9, "1x9", "usize"))
+        );
+    }
+
+    impl Case {
+        fn is_correct(&self, ops: impl Iterator<Item = Op>) -> bool {
+            self.eval(ops) == self.expected
+        }
+
+        fn eval(&self, ops: impl Iterator<Item = Op>) -> usize {
+            self.values
+                .iter()
+                .skip(1)
+                .zip(ops)
+                .fold(self.values[0], |acc, (v, op)| match op {
+                    Op::Add => acc + v,
+                    Op::Mul => acc * v,
+                    Op::Concat => digits::concat(acc, *v).expect("concatenation to fit in usize"),
+                })
+        }
+    }
+
+    /// Reference for [`Case::is_solvable`], trying every combination of `ops`.
+    fn is_solvable_naive(case: &Case, ops: &[Op]) -> bool {
+        repeat_n(ops.iter().copied(), case.values.len() - 1)
+            .multi_cartesian_product()
+            .any(|ops| case.is_correct(ops.into_iter()))
+    }
+
+    #[test]
+    fn test_is_solvable_matches_naive() {
+        let ops = [Op::Add, Op::Mul, Op::Concat];
+        // Expected values at or near the result of random operators, so both outcomes are common
+        let cases = (vec(int(0..=20), 2..=6), vec(int(0..=2), 5..=5), int(-2..=2));
+
+        prop::assert_holds(
+            Config::cases(10_000),
+            &cases,
+            |(values, choices, offset)| {
+                let values: Vec<_> = values.iter().map(|v| *v as usize).collect();
+                let expected = Case {
+                    expected: 0,
+                    values: values.clone(),
+                }
+                .eval(choices.iter().map(|c| ops[*c as usize]));
+                let Some(expected) = expected.checked_add_signed(*offset as isize) else {
+                    return true;
+                };
+                let case = Case { expected, values };
+
+                case.is_solvable(&ops) == is_solvable_naive(&case, &ops)
+                    && case.is_solvable(&ops[..2]) == is_solvable_naive(&case, &ops[..2])
+            },
+        );
+    }
 }
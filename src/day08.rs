@@ -1,31 +1,49 @@
 use itertools::Itertools;
 use std::collections::{HashMap, HashSet};
 
+use crate::grid::Grid;
 use crate::math::Vector2;
+use crate::{Answer, ParseError, Solution};
 
-pub fn star_one(input: &str) -> usize {
-    let grid = Grid::from(input);
-    grid.unique_antinodes(Some(1)).len()
+pub struct Day08;
+
+impl Solution for Day08 {
+    type Input = City;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        Grid::parse(input).map(City::from)
+    }
+
+    fn part_one(city: &Self::Input) -> Answer {
+        city.unique_antinodes(Some(1)).len().into()
+    }
+
+    fn part_two(city: &Self::Input) -> Answer {
+        city.unique_antinodes(None).len().into()
+    }
 }
 
-pub fn star_two(input: &str) -> usize {
-    let grid = Grid::from(input);
-    grid.unique_antinodes(None).len()
+pub fn star_one(input: &str) -> Answer {
+    Day08::part_one(&Day08::parse(input).expect("valid input"))
+}
+
+pub fn star_two(input: &str) -> Answer {
+    Day08::part_two(&Day08::parse(input).expect("valid input"))
 }
 
 type Frequency = char;
 
 #[derive(Debug)]
-struct Grid {
-    max: Vector2<isize>,
+pub struct City {
+    grid: Grid<char>,
     antennas: HashMap<Frequency, Vec<Antenna>>,
 }
-impl Grid {
+impl City {
     fn unique_antinodes(&self, limit: Option<usize>) -> HashSet<Vector2<isize>> {
         let unique: HashSet<Vector2<isize>> = self
             .antennas
-            .iter()
-            .flat_map(|(_, antennas)| {
+            .values()
+            .flat_map(|antennas| {
                 antennas.iter().combinations(2).flat_map(|pair| {
                     let mut a1 = anti_node(pair[0].location, pair[1].location);
                     let mut a2 = anti_node(pair[1].location, pair[0].location);
@@ -45,8 +63,8 @@ impl Grid {
                     };
 
                     // This collect is maybe avoidable, but I cannot be bothered
-                    l1.take_while(|x| self.in_bounds(*x))
-                        .chain(l2.take_while(|x| self.in_bounds(*x)))
+                    l1.take_while(|x| self.grid.in_bounds(*x))
+                        .chain(l2.take_while(|x| self.grid.in_bounds(*x)))
                         .collect::<Vec<_>>()
                         .into_iter()
                 })
@@ -55,10 +73,6 @@ impl Grid {
 
         unique
     }
-
-    fn in_bounds(&self, l: Vector2<isize>) -> bool {
-        l.x >= 0 && l.x <= self.max.x && l.y >= 0 && l.y <= self.max.y
-    }
 }
 
 fn anti_node(a1: Vector2<isize>, a2: Vector2<isize>) -> impl Iterator<Item = Vector2<isize>> {
@@ -72,53 +86,27 @@ fn anti_node(a1: Vector2<isize>, a2: Vector2<isize>) -> impl Iterator<Item = Vec
 #[derive(Debug)]
 struct Antenna {
     location: Vector2<isize>,
-    frequency: Frequency,
 }
 
-impl From<&str> for Grid {
-    fn from(input: &str) -> Self {
-        let locations = input
-            .lines()
-            .map(str::trim)
-            .filter(|l| !l.is_empty())
-            .enumerate()
-            .flat_map(|(y, row)| {
-                row.chars()
-                    .enumerate()
-                    .map(move |(x, c)| (Vector2::new(x as isize, y as isize), c))
-            });
-
-        locations.fold(
-            Grid {
-                max: Vector2::new(0, 0),
-                antennas: HashMap::new(),
-            },
-            |mut grid, (location, frequency)| {
-                grid.max.x = grid.max.x.max(location.x);
-                grid.max.y = grid.max.y.max(location.y);
-
-                if frequency == '.' {
-                    return grid;
-                }
-
-                grid.antennas
-                    .entry(frequency)
-                    .or_insert_with(Vec::new)
-                    .push(Antenna {
-                        location,
-                        frequency,
-                    });
-
-                grid
-            },
-        )
+impl From<Grid<char>> for City {
+    fn from(grid: Grid<char>) -> Self {
+        let mut antennas: HashMap<Frequency, Vec<Antenna>> = HashMap::new();
+
+        for (location, frequency) in grid.iter().filter(|(_, c)| **c != '.') {
+            antennas
+                .entry(*frequency)
+                .or_default()
+                .push(Antenna { location });
+        }
+
+        Self { grid, antennas }
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::{star_one, star_two};
-    const INPUT: &'static str = r#"
+    const INPUT: &str = r#"
 ............
 ........0...
 .....0......
@@ -1,22 +1,43 @@
-pub fn star_one(input: &str) -> i64 {
-    0
+use crate::{Answer, ParseError, Solution};
+
+pub struct Day10;
+
+impl Solution for Day10 {
+    type Input = ();
+
+    fn parse(_input: &str) -> Result<Self::Input, ParseError> {
+        Ok(())
+    }
+
+    fn part_one(_input: &Self::Input) -> Answer {
+        Answer::Unsolved
+    }
+
+    fn part_two(_input: &Self::Input) -> Answer {
+        Answer::Unsolved
+    }
+}
+
+pub fn star_one(input: &str) -> Answer {
+    Day10::part_one(&Day10::parse(input).expect("valid input"))
 }
 
-pub fn star_two(input: &str) -> i64 {
-    0
+pub fn star_two(input: &str) -> Answer {
+    Day10::part_two(&Day10::parse(input).expect("valid input"))
 }
 
 #[cfg(test)]
 mod tests {
     use super::{star_one, star_two};
+    use crate::Answer;
 
     #[test]
     fn test_star_one() {
-        assert_eq!(star_one(""), 1)
+        assert_eq!(star_one(""), Answer::Unsolved)
     }
 
     #[test]
     fn test_star_two() {
-        assert_eq!(star_two(""), 1)
+        assert_eq!(star_two(""), Answer::Unsolved)
     }
 }
@@ -0,0 +1,212 @@
+use crate::math::UnsignedInt;
+
+/// The digits of an unsigned integer.
+///
+/// Iterating forwards yields the least significant digit first, iterating backwards with
+/// [`DoubleEndedIterator::next_back`] (or [`Iterator::rev`]) yields the most significant digit
+/// first. Zero has a single digit.
+#[derive(Debug, Copy, Clone)]
+pub struct DigitIterator<T = usize> {
+    number: T,
+    radix: T,
+    /// Place value of the most significant remaining digit.
+    high: T,
+    len: usize,
+}
+
+impl<T: UnsignedInt> DigitIterator<T> {
+    /// The decimal digits of `number`.
+    pub fn new(number: T) -> Self {
+        Self::with_radix(number, 10)
+    }
+
+    /// **Note:** Panics if `radix` is less than two or doesn't fit in `T`
+    pub fn with_radix(number: T, radix: u32) -> Self {
+        let radix = radix_of(radix);
+        let mut high = T::ONE;
+        let mut len = 1;
+        while number / high >= radix {
+            high = high * radix;
+            len += 1;
+        }
+
+        Self {
+            number,
+            radix,
+            high,
+            len,
+        }
+    }
+}
+
+impl<T: UnsignedInt> Iterator for DigitIterator<T> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.len == 0 {
+            return None;
+        }
+
+        let digit = self.number % self.radix;
+        self.number = self.number / self.radix;
+        self.high = self.high / self.radix;
+        self.len -= 1;
+
+        Some(digit)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.len, Some(self.len))
+    }
+}
+
+impl<T: UnsignedInt> DoubleEndedIterator for DigitIterator<T> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        if self.len == 0 {
+            return None;
+        }
+
+        let digit = self.number / self.high;
+        self.number = self.number % self.high;
+        self.high = self.high / self.radix;
+        self.len -= 1;
+
+        Some(digit)
+    }
+}
+
+impl<T: UnsignedInt> ExactSizeIterator for DigitIterator<T> {}
+
+/// The number of decimal digits in `number`.
+pub fn count<T: UnsignedInt>(number: T) -> usize {
+    DigitIterator::new(number).len()
+}
+
+/// The decimal digits of `a` followed by those of `b`, e.g. `concat(12, 345) == Some(12345)`.
+///
+/// `None` if the result overflows `T`.
+pub fn concat<T: UnsignedInt>(a: T, b: T) -> Option<T> {
+    a.checked_mul(shift_past(b)?)?.checked_add(b)
+}
+
+/// The inverse of [`concat`], `number` without the trailing decimal digits of `suffix`, e.g.
+/// `strip_suffix(12345, 345) == Some(12)`.
+///
+/// `None` if `number` doesn't end with `suffix`.
+pub fn strip_suffix<T: UnsignedInt>(number: T, suffix: T) -> Option<T> {
+    let shift = shift_past(suffix)?;
+
+    (number % shift == suffix).then(|| number / shift)
+}
+
+/// The smallest power of ten larger than `number`, `None` if it overflows `T`.
+fn shift_past<T: UnsignedInt>(number: T) -> Option<T> {
+    let radix = radix_of::<T>(10);
+    let mut shift = radix;
+    while shift <= number {
+        shift = shift.checked_mul(radix)?;
+    }
+
+    Some(shift)
+}
+
+/// Split `number` after its first `at` decimal digits, e.g. `split(123456, 2) == (12, 3456)`.
+///
+/// **Note:** Panics if `at` is larger than the number of digits in `number`
+pub fn split<T: UnsignedInt>(number: T, at: usize) -> (T, T) {
+    let len = count(number);
+    assert!(at <= len, "Cannot split {number:?} after {at} digits");
+
+    let radix = radix_of::<T>(10);
+    let divisor = (at..len).fold(T::ONE, |d, _| d * radix);
+
+    (number / divisor, number % divisor)
+}
+
+fn radix_of<T: UnsignedInt>(radix: u32) -> T {
+    assert!(radix >= 2, "Radix must be at least 2, got {radix}");
+
+    T::try_from(radix)
+        .ok()
+        .unwrap_or_else(|| panic!("Radix {radix} doesn't fit"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{concat, count, split, strip_suffix, DigitIterator};
+
+    #[test]
+    fn test_digits() {
+        assert_eq!(
+            DigitIterator::new(1203_usize).collect::<Vec<_>>(),
+            vec![3, 0, 2, 1]
+        );
+        assert_eq!(
+            DigitIterator::new(1203_u16).rev().collect::<Vec<_>>(),
+            vec![1, 2, 0, 3]
+        );
+        assert_eq!(DigitIterator::new(0_u8).collect::<Vec<_>>(), vec![0]);
+        assert_eq!(DigitIterator::new(u64::MAX).len(), 20);
+
+        // Beyond f64 precision
+        let big = 2_u64.pow(60) + 1;
+        let digits: String = DigitIterator::new(big)
+            .rev()
+            .map(|d| char::from(b'0' + d as u8))
+            .collect();
+        assert_eq!(digits, big.to_string());
+    }
+
+    #[test]
+    fn test_digits_both_ends() {
+        let mut digits = DigitIterator::new(10502_u32);
+        assert_eq!(digits.next_back(), Some(1));
+        assert_eq!(digits.next(), Some(2));
+        assert_eq!(digits.len(), 3);
+        assert_eq!(digits.next_back(), Some(0));
+        assert_eq!(digits.next(), Some(0));
+        assert_eq!(digits.next_back(), Some(5));
+        assert_eq!(digits.next(), None);
+        assert_eq!(digits.next_back(), None);
+    }
+
+    #[test]
+    fn test_radix() {
+        assert_eq!(
+            DigitIterator::with_radix(0b1011_u8, 2).collect::<Vec<_>>(),
+            vec![1, 1, 0, 1]
+        );
+        assert_eq!(
+            DigitIterator::with_radix(0xff_u8, 16)
+                .rev()
+                .collect::<Vec<_>>(),
+            vec![15, 15]
+        );
+        assert_eq!(DigitIterator::with_radix(u128::MAX, 2).len(), 128);
+    }
+
+    #[test]
+    fn test_concat_and_split() {
+        assert_eq!(concat(12_u32, 345), Some(12345));
+        assert_eq!(concat(7_u32, 0), Some(70));
+        assert_eq!(concat(0_u32, 10), Some(10));
+        assert_eq!(concat(255_u8, 1), None);
+        assert_eq!(concat(u64::MAX / 100, 15), Some(u64::MAX));
+        assert_eq!(concat(u64::MAX / 100, 16), None);
+
+        assert_eq!(strip_suffix(12345_u32, 345), Some(12));
+        assert_eq!(strip_suffix(70_u32, 0), Some(7));
+        assert_eq!(strip_suffix(10_u32, 10), Some(0));
+        assert_eq!(strip_suffix(12345_u32, 45), Some(123));
+        assert_eq!(strip_suffix(12345_u32, 5), Some(1234));
+        assert_eq!(strip_suffix(12345_u32, 46), None);
+        assert_eq!(strip_suffix(5_u8, 255), None);
+
+        assert_eq!(count(9_usize), 1);
+        assert_eq!(count(10_usize), 2);
+        assert_eq!(split(123456_u32, 2), (12, 3456));
+        assert_eq!(split(1000_u32, 2), (10, 0));
+        assert_eq!(split(42_u32, 0), (0, 42));
+        assert_eq!(split(42_u32, 2), (42, 0));
+    }
+}
@@ -0,0 +1,107 @@
+use std::fmt;
+use std::str::FromStr;
+
+/// An error encountered while parsing puzzle input.
+///
+/// Lines and columns are 1-based, columns count characters rather than bytes.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError {
+    pub line: usize,
+    pub column: usize,
+    /// The offending text.
+    pub text: String,
+    /// Name of the type that was being parsed or a description of what was expected.
+    pub expected: String,
+}
+
+impl ParseError {
+    pub fn new(
+        line: usize,
+        column: usize,
+        text: impl Into<String>,
+        expected: impl Into<String>,
+    ) -> Self {
+        Self {
+            line,
+            column,
+            text: text.into(),
+            expected: expected.into(),
+        }
+    }
+
+    /// Create an error for `fragment`, a sub slice of `input`, with its position within `input`.
+    ///
+    /// **Note:** Panics if `fragment` is not a sub slice of `input`
+    pub fn at(input: &str, fragment: &str, expected: impl Into<String>) -> Self {
+        let (line, column) = position(input, fragment);
+
+        Self::new(line, column, fragment, expected)
+    }
+
+    /// Create an error for when `T` could not be parsed from `fragment`, a sub slice of `input`.
+    pub fn for_type<T>(input: &str, fragment: &str) -> Self {
+        Self::at(input, fragment, std::any::type_name::<T>())
+    }
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "line {}, column {}: expected {} but found `{}`",
+            self.line, self.column, self.expected, self.text
+        )
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+/// Parse `fragment`, a sub slice of `input`, reporting its position within `input` on failure.
+pub fn try_parse<T: FromStr>(input: &str, fragment: &str) -> Result<T, ParseError> {
+    fragment
+        .parse()
+        .map_err(|_| ParseError::for_type::<T>(input, fragment))
+}
+
+/// Line and column of `fragment` within `input`, both 1-based.
+fn position(input: &str, fragment: &str) -> (usize, usize) {
+    let offset = (fragment.as_ptr() as usize)
+        .checked_sub(input.as_ptr() as usize)
+        .filter(|o| o + fragment.len() <= input.len())
+        .expect("Fragment should be a sub slice of input");
+    let before = &input[..offset];
+    let line_start = before.rfind('\n').map(|i| i + 1).unwrap_or(0);
+
+    (
+        before.matches('\n').count() + 1,
+        before[line_start..].chars().count() + 1,
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{try_parse, ParseError};
+
+    #[test]
+    fn test_at() {
+        let input = "1 2\n  3 x4 5\n";
+        let error = ParseError::at(input, &input[8..10], "number");
+
+        assert_eq!(error, ParseError::new(2, 5, "x4", "number"));
+        assert_eq!(
+            error.to_string(),
+            "line 2, column 5: expected number but found `x4`"
+        );
+    }
+
+    #[test]
+    fn test_try_parse() {
+        let input = "12,-4\n7,y";
+
+        assert_eq!(try_parse::<i64>(input, &input[3..5]), Ok(-4));
+        assert_eq!(
+            try_parse::<i64>(input, &input[8..]),
+            Err(ParseError::new(2, 3, "y", "i64"))
+        );
+    }
+}
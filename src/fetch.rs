@@ -0,0 +1,579 @@
+use std::env;
+use std::fmt;
+use std::fs;
+use std::io::{self, BufRead, BufReader, Write};
+use std::net::TcpStream;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+use std::time::Duration;
+
+use crate::input::Inputs;
+
+/// Environment variable holding the session token, takes precedence over the session file.
+pub const SESSION_ENV: &str = "AOC_SESSION";
+/// The session file within the config directory, `$XDG_CONFIG_HOME` or `~/.config`.
+pub const SESSION_FILE: &str = "aoc/session";
+pub const BASE_URL: &str = "https://adventofcode.com";
+pub const YEAR: u16 = 2024;
+/// Identifies this tool to the site as its operators ask automated tools to do.
+pub const USER_AGENT: &str = concat!(
+    "advent-of-rust-2024/",
+    env!("CARGO_PKG_VERSION"),
+    " (+https://github.com/k0nserv/advent-of-rust-2024)"
+);
+
+/// How long a request may take before it is abandoned.
+const TIMEOUT: Duration = Duration::from_secs(30);
+
+/// The value of the `session` cookie of a logged in browser.
+#[derive(Clone, PartialEq, Eq)]
+pub struct Session(String);
+
+impl Session {
+    /// Only non-empty printable ASCII without whitespace is accepted, as the token ends up in a
+    /// request header and in curl's config.
+    pub fn new(token: impl Into<String>) -> Result<Self, FetchError> {
+        let token = token.into();
+        if token.is_empty() || !token.bytes().all(|b| b.is_ascii_graphic()) {
+            return Err(FetchError::InvalidSession);
+        }
+
+        Ok(Self(token))
+    }
+
+    /// The token in [`SESSION_ENV`], or else the one in the session file.
+    pub fn load() -> Result<Self, FetchError> {
+        let token = env::var(SESSION_ENV).ok().filter(|t| !t.trim().is_empty());
+        if let Some(token) = token {
+            return Self::new(token.trim());
+        }
+
+        match session_path() {
+            Some(path) if path.is_file() => Self::from_file(&path),
+            _ => Err(FetchError::NoSession),
+        }
+    }
+
+    /// The token in the file at `path`, surrounding whitespace is ignored.
+    pub fn from_file(path: &Path) -> Result<Self, FetchError> {
+        let token = fs::read_to_string(path).map_err(|source| FetchError::Io {
+            path: path.to_path_buf(),
+            source,
+        })?;
+
+        match token.trim() {
+            "" => Err(FetchError::NoSession),
+            token => Self::new(token),
+        }
+    }
+}
+
+impl fmt::Debug for Session {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        // Never leak the token into logs
+        write!(f, "Session(..)")
+    }
+}
+
+/// Where the session file is expected, `None` if there is no config or home directory.
+pub fn session_path() -> Option<PathBuf> {
+    let config = env::var_os("XDG_CONFIG_HOME")
+        .filter(|d| !d.is_empty())
+        .map(PathBuf::from)
+        .or_else(|| env::var_os("HOME").map(|home| PathBuf::from(home).join(".config")))?;
+
+    Some(config.join(SESSION_FILE))
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Request {
+    pub url: String,
+    pub headers: Vec<(&'static str, String)>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Response {
+    pub status: u16,
+    pub body: String,
+}
+
+/// Performs a single HTTP GET.
+pub trait Transport {
+    fn get(&self, request: &Request) -> io::Result<Response>;
+}
+
+/// Plain HTTP/1.1 over a TCP connection, for `http://` URLs only. This can't reach [`BASE_URL`],
+/// use [`Curl`] for that.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Tcp;
+
+impl Transport for Tcp {
+    fn get(&self, request: &Request) -> io::Result<Response> {
+        let (host, path) = request
+            .url
+            .strip_prefix("http://")
+            .map(|rest| rest.split_once('/').unwrap_or((rest, "")))
+            .ok_or_else(|| invalid(format!("unsupported URL {}", request.url)))?;
+
+        let mut stream = TcpStream::connect(host)?;
+        stream.set_read_timeout(Some(TIMEOUT))?;
+        stream.set_write_timeout(Some(TIMEOUT))?;
+
+        let mut head = format!("GET /{path} HTTP/1.1\r\nHost: {host}\r\nConnection: close\r\n");
+        for (name, value) in &request.headers {
+            head.push_str(&format!("{name}: {value}\r\n"));
+        }
+        head.push_str("\r\n");
+        stream.write_all(head.as_bytes())?;
+
+        read_response(BufReader::new(stream))
+    }
+}
+
+fn read_response(mut reader: impl BufRead) -> io::Result<Response> {
+    let mut line = String::new();
+    reader.read_line(&mut line)?;
+    let status = line
+        .split_whitespace()
+        .nth(1)
+        .and_then(|s| s.parse().ok())
+        .ok_or_else(|| invalid(format!("malformed status line {:?}", line.trim())))?;
+
+    let (mut length, mut chunked) = (None, false);
+    loop {
+        line.clear();
+        if reader.read_line(&mut line)? == 0 || line.trim().is_empty() {
+            break;
+        }
+
+        let Some((name, value)) = line.split_once(':') else {
+            continue;
+        };
+        let value = value.trim();
+        if name.eq_ignore_ascii_case("content-length") {
+            length = Some(
+                value
+                    .parse()
+                    .map_err(|_| invalid("malformed content length"))?,
+            );
+        } else if name.eq_ignore_ascii_case("transfer-encoding") {
+            chunked = value.eq_ignore_ascii_case("chunked");
+        }
+    }
+
+    let body = if chunked {
+        read_chunked(&mut reader)?
+    } else if let Some(length) = length {
+        let mut body = vec![0; length];
+        reader.read_exact(&mut body)?;
+        body
+    } else {
+        let mut body = vec![];
+        reader.read_to_end(&mut body)?;
+        body
+    };
+
+    let body = String::from_utf8(body).map_err(|_| invalid("body is not UTF-8"))?;
+
+    Ok(Response { status, body })
+}
+
+fn read_chunked(reader: &mut impl BufRead) -> io::Result<Vec<u8>> {
+    let mut body = vec![];
+    let mut line = String::new();
+
+    loop {
+        line.clear();
+        reader.read_line(&mut line)?;
+        // Chunk extensions after `;` are allowed but meaningless here
+        let size = line.split(';').next().unwrap_or_default().trim();
+        let size = usize::from_str_radix(size, 16).map_err(|_| invalid("malformed chunk size"))?;
+        if size == 0 {
+            return Ok(body);
+        }
+
+        let start = body.len();
+        body.resize(start + size, 0);
+        reader.read_exact(&mut body[start..])?;
+        // The CRLF after every chunk
+        reader.read_line(&mut line)?;
+    }
+}
+
+fn invalid(message: impl Into<String>) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, message.into())
+}
+
+/// HTTP and HTTPS by running the `curl` executable, which has to be on `PATH`. This is the only
+/// transport that reaches [`BASE_URL`].
+///
+/// Headers are passed on stdin so the session token doesn't show up in the process list.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Curl;
+
+impl Transport for Curl {
+    fn get(&self, request: &Request) -> io::Result<Response> {
+        let mut child = Command::new("curl")
+            .args(["--silent", "--show-error", "--config", "-"])
+            .args(["--max-time", &TIMEOUT.as_secs().to_string()])
+            .args(["--write-out", "\n%{http_code}"])
+            .arg(&request.url)
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .spawn()
+            .map_err(|e| match e.kind() {
+                io::ErrorKind::NotFound => io::Error::new(e.kind(), "curl isn't installed"),
+                _ => e,
+            })?;
+
+        let mut config = String::new();
+        for (name, value) in &request.headers {
+            let header = format!("{name}: {value}")
+                .replace('\\', "\\\\")
+                .replace('"', "\\\"");
+            config.push_str(&format!("header = \"{header}\"\n"));
+        }
+        child
+            .stdin
+            .take()
+            .expect("stdin is piped")
+            .write_all(config.as_bytes())?;
+
+        let output = child.wait_with_output()?;
+        if !output.status.success() {
+            let stderr = String::from_utf8_lossy(&output.stderr);
+            return Err(io::Error::other(format!("curl failed: {}", stderr.trim())));
+        }
+
+        let stdout = String::from_utf8(output.stdout).map_err(|_| invalid("body is not UTF-8"))?;
+        let (body, status) = stdout
+            .rsplit_once('\n')
+            .ok_or_else(|| invalid("missing status from curl"))?;
+        let status = status
+            .parse()
+            .map_err(|_| invalid(format!("malformed status {status:?} from curl")))?;
+
+        Ok(Response {
+            status,
+            body: body.to_owned(),
+        })
+    }
+}
+
+/// What [`Downloader::fetch`] did to provide an input.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Fetched {
+    /// The input was already at this path, nothing was requested.
+    Cached(PathBuf),
+    /// The input was downloaded and saved to this path.
+    Downloaded(PathBuf),
+}
+
+impl Fetched {
+    pub fn path(&self) -> &Path {
+        match self {
+            Self::Cached(path) | Self::Downloaded(path) => path,
+        }
+    }
+}
+
+/// Downloads puzzle inputs into an input directory, each day is requested at most once.
+#[derive(Debug)]
+pub struct Downloader<T> {
+    transport: T,
+    session: Session,
+    base_url: String,
+}
+
+impl<T: Transport> Downloader<T> {
+    pub fn new(transport: T, session: Session) -> Self {
+        Self {
+            transport,
+            session,
+            base_url: BASE_URL.to_owned(),
+        }
+    }
+
+    pub fn with_base_url(self, base_url: impl Into<String>) -> Self {
+        Self {
+            base_url: base_url.into(),
+            ..self
+        }
+    }
+
+    /// Make sure the input for `day` is in `inputs`, downloading it only if it isn't there yet.
+    pub fn fetch(&self, inputs: &Inputs, day: u8) -> Result<Fetched, FetchError> {
+        let path = inputs.path(day);
+        if path.is_file() {
+            return Ok(Fetched::Cached(path));
+        }
+
+        let request = Request {
+            url: format!(
+                "{}/{YEAR}/day/{day}/input",
+                self.base_url.trim_end_matches('/')
+            ),
+            headers: vec![
+                ("User-Agent", USER_AGENT.to_owned()),
+                ("Cookie", format!("session={}", self.session.0)),
+            ],
+        };
+        let response = self
+            .transport
+            .get(&request)
+            .map_err(FetchError::Transport)?;
+        if response.status != 200 {
+            return Err(FetchError::Status {
+                day,
+                status: response.status,
+                body: response.body,
+            });
+        }
+
+        // Write next to the destination and rename, so an interrupted download never looks cached
+        let io_error = |source| FetchError::Io {
+            path: path.clone(),
+            source,
+        };
+        fs::create_dir_all(inputs.dir()).map_err(io_error)?;
+        let partial = path.with_extension("txt.partial");
+        fs::write(&partial, response.body).map_err(io_error)?;
+        fs::rename(&partial, &path).map_err(io_error)?;
+
+        Ok(Fetched::Downloaded(path))
+    }
+}
+
+#[derive(Debug)]
+pub enum FetchError {
+    /// Neither [`SESSION_ENV`] nor the session file has a token.
+    NoSession,
+    /// The token isn't printable ASCII without whitespace, see [`Session::new`].
+    InvalidSession,
+    Transport(io::Error),
+    /// The server answered with something other than 200 OK.
+    Status {
+        day: u8,
+        status: u16,
+        body: String,
+    },
+    /// Reading the session file or writing the input at `path` failed.
+    Io {
+        path: PathBuf,
+        source: io::Error,
+    },
+}
+
+impl fmt::Display for FetchError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::NoSession => write!(
+                f,
+                "no session token, set {SESSION_ENV} or write it to ~/.config/{SESSION_FILE}"
+            ),
+            Self::InvalidSession => write!(
+                f,
+                "session token must be printable ASCII without whitespace, copy the `session` cookie's value"
+            ),
+            Self::Transport(e) => write!(f, "request failed: {e}"),
+            Self::Status { day, status, .. } if *status == 404 => {
+                write!(f, "input for day {day} isn't available yet (404)")
+            }
+            Self::Status { status, .. } if *status == 400 => {
+                write!(f, "session token was rejected (400), log in again")
+            }
+            Self::Status { status, body, .. } => {
+                write!(
+                    f,
+                    "unexpected status {status}: {}",
+                    body.lines().next().unwrap_or("")
+                )
+            }
+            Self::Io { path, source } => write!(f, "unable to access {}: {source}", path.display()),
+        }
+    }
+}
+
+impl std::error::Error for FetchError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::Transport(e) | Self::Io { source: e, .. } => Some(e),
+            Self::NoSession | Self::InvalidSession | Self::Status { .. } => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+    use std::io::{BufRead, BufReader, Write};
+    use std::net::TcpListener;
+    use std::process::Command;
+    use std::sync::{Arc, Mutex};
+    use std::thread;
+
+    use super::{
+        read_response, Curl, Downloader, FetchError, Fetched, Session, Tcp, Transport, USER_AGENT,
+    };
+    use crate::input::Inputs;
+
+    /// A local server answering `/2024/day/1/input`, rejecting other sessions with 400 and other
+    /// days with 404. Returns its base URL and the request heads it received.
+    fn stub_server() -> (String, Arc<Mutex<Vec<String>>>) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let base_url = format!("http://{}", listener.local_addr().unwrap());
+        let requests = Arc::new(Mutex::new(vec![]));
+
+        let seen = Arc::clone(&requests);
+        thread::spawn(move || {
+            for stream in listener.incoming() {
+                let mut stream = stream.unwrap();
+                let mut head = String::new();
+                let mut reader = BufReader::new(&stream);
+                // Up to the empty line ending the head
+                while reader.read_line(&mut head).unwrap() > 2 {}
+
+                let response = if !head.contains("Cookie: session=secret\r\n") {
+                    "HTTP/1.1 400 Bad Request\r\nContent-Length: 9\r\n\r\nLog in..."
+                } else if head.starts_with("GET /2024/day/1/input ") {
+                    // Chunked like the real site
+                    "HTTP/1.1 200 OK\r\nTransfer-Encoding: chunked\r\n\r\n6\r\n3   4\n\r\n6\r\n4   3\n\r\n0\r\n\r\n"
+                } else {
+                    "HTTP/1.1 404 Not Found\r\nContent-Length: 0\r\n\r\n"
+                };
+                seen.lock().unwrap().push(head);
+                stream.write_all(response.as_bytes()).unwrap();
+            }
+        });
+
+        (base_url, requests)
+    }
+
+    fn temp_inputs(name: &str) -> Inputs {
+        let dir = std::env::temp_dir().join(format!("aoc-fetch-{name}-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+
+        Inputs::new(dir)
+    }
+
+    fn check_transport(name: &str, transport: impl Transport + Copy) {
+        let (base_url, requests) = stub_server();
+        let inputs = temp_inputs(name).with_user("alice");
+        let downloader =
+            Downloader::new(transport, Session::new("secret").unwrap()).with_base_url(&base_url);
+
+        let fetched = downloader.fetch(&inputs, 1).unwrap();
+        assert_eq!(fetched, Fetched::Downloaded(inputs.path(1)));
+        assert_eq!(inputs.load(1).unwrap(), "3   4\n4   3\n");
+        {
+            let requests = requests.lock().unwrap();
+            assert_eq!(requests.len(), 1);
+            assert!(requests[0].starts_with("GET /2024/day/1/input HTTP/1.1\r\n"));
+            assert!(requests[0].contains(&format!("User-Agent: {USER_AGENT}\r\n")));
+        }
+
+        // Never requested again once cached
+        assert_eq!(
+            downloader.fetch(&inputs, 1).unwrap(),
+            Fetched::Cached(inputs.path(1))
+        );
+        assert_eq!(requests.lock().unwrap().len(), 1);
+
+        let error = downloader.fetch(&inputs, 2).unwrap_err();
+        assert!(matches!(error, FetchError::Status { status: 404, .. }));
+        assert!(!inputs.exists(2));
+
+        let rejected =
+            Downloader::new(transport, Session::new("stale").unwrap()).with_base_url(&base_url);
+        let error = rejected.fetch(&inputs, 3).unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "session token was rejected (400), log in again"
+        );
+        assert!(!inputs.exists(3));
+
+        fs::remove_dir_all(inputs.dir().parent().unwrap()).unwrap();
+    }
+
+    #[test]
+    fn test_tcp() {
+        check_transport("tcp", Tcp);
+
+        let error = Downloader::new(Tcp, Session::new("secret").unwrap())
+            .fetch(&temp_inputs("https"), 1)
+            .unwrap_err();
+        assert!(error.to_string().contains("unsupported URL https://"));
+    }
+
+    #[test]
+    fn test_curl() {
+        // `aoc fetch` can't work without curl, so neither can this test
+        assert!(
+            Command::new("curl").arg("--version").output().is_ok(),
+            "curl is required to fetch inputs"
+        );
+
+        check_transport("curl", Curl);
+    }
+
+    #[test]
+    fn test_read_response() {
+        let response =
+            read_response("HTTP/1.1 200 OK\r\nContent-Length: 5\r\n\r\nhello, extra".as_bytes())
+                .unwrap();
+        assert_eq!((response.status, response.body.as_str()), (200, "hello"));
+
+        let response = read_response("HTTP/1.0 500 Oops\r\n\r\nuntil close".as_bytes()).unwrap();
+        assert_eq!(response.body, "until close");
+
+        assert!(read_response("nonsense\r\n\r\n".as_bytes()).is_err());
+    }
+
+    #[test]
+    fn test_session() {
+        let path = std::env::temp_dir().join(format!("aoc-session-{}", std::process::id()));
+
+        fs::write(&path, "  abc123\n").unwrap();
+        assert_eq!(
+            Session::from_file(&path).unwrap(),
+            Session::new("abc123").unwrap()
+        );
+        assert_eq!(
+            format!("{:?}", Session::new("abc123").unwrap()),
+            "Session(..)"
+        );
+
+        // Anything that could end a header or a line of curl's config
+        for token in [
+            "",
+            "abc\n123",
+            "abc\r\nHost: evil",
+            "abc 123",
+            "abc\t123",
+            "abcé",
+        ] {
+            assert!(
+                matches!(Session::new(token), Err(FetchError::InvalidSession)),
+                "{token:?}"
+            );
+        }
+        fs::write(&path, "abc\noutput = \"/tmp/x\"\n").unwrap();
+        assert!(matches!(
+            Session::from_file(&path),
+            Err(FetchError::InvalidSession)
+        ));
+
+        fs::write(&path, "\n").unwrap();
+        assert!(matches!(
+            Session::from_file(&path),
+            Err(FetchError::NoSession)
+        ));
+
+        fs::remove_file(&path).unwrap();
+        assert!(matches!(
+            Session::from_file(&path),
+            Err(FetchError::Io { .. })
+        ));
+    }
+}
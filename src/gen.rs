@@ -0,0 +1,518 @@
+use std::collections::HashSet;
+use std::fmt::Write;
+
+use crate::digits::concat;
+use crate::rng::Rng;
+use crate::Answer;
+
+/// A generated puzzle input and the answers known from how it was built.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Generated {
+    pub input: String,
+    pub part_one: Option<Answer>,
+    pub part_two: Option<Answer>,
+}
+
+impl Generated {
+    fn new(input: String) -> Self {
+        Self {
+            input,
+            part_one: None,
+            part_two: None,
+        }
+    }
+}
+
+/// A seeded input for `day` of roughly `size` lines, or `size` by `size` cells for grids.
+/// `None` for days without a generator.
+pub fn generate(day: u8, rng: &mut Rng, size: usize) -> Option<Generated> {
+    let generated = match day {
+        1 => day01(rng, size),
+        2 => day02(rng, size),
+        3 => day03(rng, size),
+        4 => day04(rng, size),
+        5 => day05(rng, size),
+        6 => day06(rng, size),
+        7 => day07(rng, size),
+        8 => day08(rng, size),
+        _ => return None,
+    };
+
+    Some(generated)
+}
+
+/// `size` pairs of five digit location IDs. Both answers are known.
+pub fn day01(rng: &mut Rng, size: usize) -> Generated {
+    let mut left: Vec<i64> = (0..size).map(|_| rng.range(10000..=99999)).collect();
+    let mut right: Vec<i64> = (0..size)
+        .map(|i| {
+            // Reuse IDs from the left list now and then so the similarity score isn't zero
+            if rng.chance(0.3) {
+                left[i]
+            } else {
+                rng.range(10000..=99999)
+            }
+        })
+        .collect();
+
+    left.sort();
+    right.sort();
+    let distance: u64 = left.iter().zip(&right).map(|(a, b)| a.abs_diff(*b)).sum();
+    let similarity: i64 = left
+        .iter()
+        .map(|l| l * right.iter().filter(|r| *r == l).count() as i64)
+        .sum();
+
+    rng.shuffle(&mut left);
+    rng.shuffle(&mut right);
+    let mut input = String::new();
+    for (l, r) in left.iter().zip(&right) {
+        let _ = writeln!(input, "{l}   {r}");
+    }
+
+    Generated {
+        part_one: Some(distance.into()),
+        part_two: Some(similarity.into()),
+        ..Generated::new(input)
+    }
+}
+
+/// `size` reports of five to eight levels, about a third each safe, safe after removing a
+/// level and unsafe regardless. Both answers are known.
+pub fn day02(rng: &mut Rng, size: usize) -> Generated {
+    let mut input = String::new();
+    let (mut safe, mut tolerable) = (0, 0);
+
+    for _ in 0..size {
+        let len = rng.range(5..=8) as usize;
+        let direction = if rng.chance(0.5) { 1 } else { -1 };
+        let mut levels = vec![rng.range(30..=60)];
+        for _ in 1..len {
+            let last = levels[levels.len() - 1];
+            levels.push(last + direction * rng.range(1..=3));
+        }
+
+        // Repeating a level is never safe, but removing the repeat fixes it
+        match rng.index(3) {
+            0 => safe += 1,
+            1 => {
+                let i = rng.index(len);
+                levels.insert(i, levels[i]);
+                tolerable += 1;
+            }
+            _ => {
+                let i = rng.index(len - 2);
+                levels.insert(i + 2, levels[i + 1]);
+                levels.insert(i, levels[i]);
+            }
+        }
+
+        let line = levels.iter().map(i64::to_string).collect::<Vec<_>>();
+        let _ = writeln!(input, "{}", line.join(" "));
+    }
+
+    Generated {
+        part_one: Some(safe.into()),
+        part_two: Some((safe + tolerable).into()),
+        ..Generated::new(input)
+    }
+}
+
+/// Corrupted memory with `size` lines of junk, valid instructions and near misses. Both
+/// answers are known.
+pub fn day03(rng: &mut Rng, size: usize) -> Generated {
+    // Neither `m` nor `d` so junk can't start an instruction by accident
+    const JUNK: &[u8] = b"!@#$%^&*()[]{}<>?+-_=;:'\", 0123456789abcefghijkpqrsxyz";
+    const NEAR_MISSES: [&str; 6] = [
+        "mul[3,7]",
+        "mul(4*",
+        "mul ( 2 , 4 )",
+        "mul(32,64]",
+        "do_not_",
+        "don't(]",
+    ];
+
+    let mut input = String::new();
+    let (mut all, mut enabled, mut active) = (0, 0, true);
+
+    for _ in 0..size {
+        for _ in 0..rng.range(20..=60) {
+            match rng.index(10) {
+                0 => {
+                    let (lhs, rhs) = (rng.range(0..=999), rng.range(0..=999));
+                    let comma = *rng.choose(&[",", ",", ", ", " ,\n"]);
+                    let _ = write!(input, "mul({lhs}{comma}{rhs})");
+
+                    all += lhs * rhs;
+                    if active {
+                        enabled += lhs * rhs;
+                    }
+                }
+                1 => {
+                    active = rng.chance(0.5);
+                    input.push_str(if active { "do()" } else { "don't()" });
+                }
+                2 => input.push_str(NEAR_MISSES[rng.index(NEAR_MISSES.len())]),
+                _ => input.push(char::from(*rng.choose(JUNK))),
+            }
+        }
+        input.push('\n');
+    }
+
+    Generated {
+        part_one: Some(all.into()),
+        part_two: Some(enabled.into()),
+        ..Generated::new(input)
+    }
+}
+
+/// A `size` by `size` word search of `M` with `XMAS` words, `X-MAS` crosses and near misses of
+/// both planted in 4 by 4 squares. Both answers are known.
+///
+/// Only plants have an `X`, `A` or `S` and they are at least four cells apart, so no match can
+/// span two of them or use the background.
+pub fn day04(rng: &mut Rng, size: usize) -> Generated {
+    const DIRECTIONS: [(isize, isize); 8] = [
+        (0, -1),
+        (1, -1),
+        (1, 0),
+        (1, 1),
+        (0, 1),
+        (-1, 1),
+        (-1, 0),
+        (-1, -1),
+    ];
+    // Clockwise from the top left, the last is a near miss with `MAM` and `SAS` diagonals
+    const CORNERS: [[char; 4]; 5] = [
+        ['M', 'M', 'S', 'S'],
+        ['S', 'M', 'M', 'S'],
+        ['S', 'S', 'M', 'M'],
+        ['M', 'S', 'S', 'M'],
+        ['M', 'S', 'M', 'S'],
+    ];
+
+    let mut cells = vec!['M'; size * size];
+    let (mut words, mut crosses) = (0, 0);
+
+    for block in 0..(size / 7) * (size / 7) {
+        let (x0, y0) = (block % (size / 7) * 7 + 1, block / (size / 7) * 7 + 1);
+        let mut plant =
+            |x: isize, y: isize, c| cells[(y0 + y as usize) * size + x0 + x as usize] = c;
+
+        match rng.index(5) {
+            kind @ (0 | 1) => {
+                let (dx, dy) = *rng.choose(&DIRECTIONS);
+                let start = |d: isize, rng: &mut Rng| match d {
+                    0 => rng.range(0..=3) as isize,
+                    1 => 0,
+                    _ => 3,
+                };
+                let (x, y) = (start(dx, rng), start(dy, rng));
+                // The near miss stops short of the `S`
+                let word = if kind == 0 { "XMAS" } else { "XMA" };
+                for (i, c) in (0..).zip(word.chars()) {
+                    plant(x + i * dx, y + i * dy, c);
+                }
+                if kind == 0 {
+                    words += 1;
+                }
+            }
+            kind @ (2 | 3) => {
+                let corners = if kind == 2 {
+                    CORNERS[rng.index(4)]
+                } else {
+                    CORNERS[4]
+                };
+                let (x, y) = (rng.range(0..=1) as isize, rng.range(0..=1) as isize);
+                plant(x + 1, y + 1, 'A');
+                for ((dx, dy), c) in [(0, 0), (2, 0), (2, 2), (0, 2)].into_iter().zip(corners) {
+                    plant(x + dx, y + dy, c);
+                }
+                if kind == 2 {
+                    crosses += 1;
+                }
+            }
+            _ => {}
+        }
+    }
+
+    let mut input = String::new();
+    for row in cells.chunks(size) {
+        let _ = writeln!(input, "{}", row.iter().collect::<String>());
+    }
+
+    Generated {
+        part_one: Some(words.into()),
+        part_two: Some(crosses.into()),
+        ..Generated::new(input)
+    }
+}
+
+/// Rules for every pair of pages in a hidden total order and `size` updates, about half of them
+/// in the right order. Both answers are known.
+pub fn day05(rng: &mut Rng, size: usize) -> Generated {
+    let mut order: Vec<usize> = (10..=99).collect();
+    rng.shuffle(&mut order);
+    order.truncate(49);
+
+    let mut input = String::new();
+    let mut rules = vec![];
+    for (i, before) in order.iter().enumerate() {
+        for after in &order[i + 1..] {
+            rules.push((before, after));
+        }
+    }
+    rng.shuffle(&mut rules);
+    for (before, after) in rules {
+        let _ = writeln!(input, "{before}|{after}");
+    }
+    input.push('\n');
+
+    let (mut ordered, mut reordered) = (0, 0);
+    for _ in 0..size {
+        let len = 2 * rng.range(2..=11) as usize + 1;
+        let mut indices: Vec<_> = (0..order.len()).collect();
+        rng.shuffle(&mut indices);
+        indices.truncate(len);
+        indices.sort();
+
+        let mut update: Vec<_> = indices.iter().map(|i| order[*i]).collect();
+        let middle = update[len / 2];
+        if rng.chance(0.5) {
+            ordered += middle;
+        } else {
+            // Make sure shuffling actually breaks the order
+            while is_sorted(&update, &order) {
+                rng.shuffle(&mut update);
+            }
+            reordered += middle;
+        }
+
+        let update: Vec<_> = update.iter().map(usize::to_string).collect();
+        let _ = writeln!(input, "{}", update.join(","));
+    }
+
+    Generated {
+        part_one: Some(ordered.into()),
+        part_two: Some(reordered.into()),
+        ..Generated::new(input)
+    }
+}
+
+fn is_sorted(update: &[usize], order: &[usize]) -> bool {
+    let rank = |page: &usize| order.iter().position(|p| p == page);
+
+    update.windows(2).all(|w| rank(&w[0]) < rank(&w[1]))
+}
+
+/// A `size` by `size` lab with scattered obstructions, the guard visits at least `size` positions
+/// before leaving. Both answers are known.
+///
+/// **Note:** Panics if `size` is zero
+pub fn day06(rng: &mut Rng, size: usize) -> Generated {
+    assert!(size > 0, "Size must be positive");
+
+    loop {
+        let guard = rng.index(size * size);
+        let cells: Vec<u8> = (0..size * size)
+            .map(|i| {
+                if i == guard {
+                    b'^'
+                } else if rng.chance(0.1) {
+                    b'#'
+                } else {
+                    b'.'
+                }
+            })
+            .collect();
+
+        // Short patrols rarely leave room for a loop
+        let Some(visited) = patrol(&cells, size, guard, None).filter(|v| v.len() >= size) else {
+            continue;
+        };
+        // An obstruction off the path changes nothing
+        let loops = visited
+            .iter()
+            .filter(|at| **at != guard && patrol(&cells, size, guard, Some(**at)).is_none())
+            .count();
+
+        let mut input = String::new();
+        for row in cells.chunks(size) {
+            let _ = writeln!(input, "{}", String::from_utf8_lossy(row));
+        }
+
+        return Generated {
+            part_one: Some(visited.len().into()),
+            part_two: Some(loops.into()),
+            ..Generated::new(input)
+        };
+    }
+}
+
+/// The cells the guard starting at `guard` facing up visits before leaving, with an extra
+/// obstruction at `extra`. `None` if the guard gets stuck in a loop.
+fn patrol(cells: &[u8], size: usize, guard: usize, extra: Option<usize>) -> Option<HashSet<usize>> {
+    const DELTAS: [(isize, isize); 4] = [(0, -1), (1, 0), (0, 1), (-1, 0)];
+
+    let (mut x, mut y, mut facing) = ((guard % size) as isize, (guard / size) as isize, 0);
+    let mut visited = HashSet::from([guard]);
+    let mut seen = HashSet::from([(guard, facing)]);
+
+    loop {
+        let (dx, dy) = DELTAS[facing];
+        let (nx, ny) = (x + dx, y + dy);
+        if !(0..size as isize).contains(&nx) || !(0..size as isize).contains(&ny) {
+            return Some(visited);
+        }
+
+        let next = ny as usize * size + nx as usize;
+        if cells[next] == b'#' || extra == Some(next) {
+            facing = (facing + 1) % 4;
+        } else {
+            (x, y) = (nx, ny);
+            visited.insert(next);
+        }
+
+        if !seen.insert((y as usize * size + x as usize, facing)) {
+            return None;
+        }
+    }
+}
+
+/// `size` calibration equations of two to eight values, each solvable with addition,
+/// multiplication and concatenation. Both answers are known.
+///
+/// Values have at most two digits so no combination of operators can overflow.
+pub fn day07(rng: &mut Rng, size: usize) -> Generated {
+    let mut input = String::new();
+    let (mut without_concat, mut total) = (0, 0);
+
+    for _ in 0..size {
+        let values: Vec<usize> = (0..rng.range(2..=8))
+            .map(|_| rng.range(1..=99) as usize)
+            .collect();
+        let expected = values[1..]
+            .iter()
+            .fold(values[0], |acc, v| match rng.index(3) {
+                0 => acc + v,
+                1 => acc * v,
+                _ => concat(acc, *v).expect("at most 16 digits"),
+            });
+        total += expected;
+        // Every combination of addition and multiplication, one bit per operator
+        if (0..1 << (values.len() - 1)).any(|mask: usize| {
+            let mut ops = (0..).map(|i| mask >> i & 1);
+            values[1..]
+                .iter()
+                .fold(values[0], |acc, v| match ops.next() {
+                    Some(0) => acc + v,
+                    _ => acc * v,
+                })
+                == expected
+        }) {
+            without_concat += expected;
+        }
+
+        let values: Vec<_> = values.iter().map(usize::to_string).collect();
+        let _ = writeln!(input, "{expected}: {}", values.join(" "));
+    }
+
+    Generated {
+        part_one: Some(without_concat.into()),
+        part_two: Some(total.into()),
+        ..Generated::new(input)
+    }
+}
+
+/// A `size` by `size` city with a few antennas for each of up to eight frequencies. Both
+/// answers are known.
+pub fn day08(rng: &mut Rng, size: usize) -> Generated {
+    const FREQUENCIES: &[u8] = b"0123456789abcdefghijklmnopqrstuvwxyzABCDEFGHIJKLMNOPQRSTUVWXYZ";
+
+    let mut cells = vec!['.'; size * size];
+    for _ in 0..rng.range(1..=8) {
+        let frequency = char::from(*rng.choose(FREQUENCIES));
+        for _ in 0..rng.range(2..=5) {
+            cells[rng.index(size * size)] = frequency;
+        }
+    }
+
+    // Later antennas may have replaced earlier ones, so pair up what ended up in the city
+    let at = |i: usize| ((i % size) as isize, (i / size) as isize);
+    let in_bounds =
+        |(x, y): (isize, isize)| (0..size as isize).contains(&x) && (0..size as isize).contains(&y);
+    let (mut adjacent, mut in_line) = (HashSet::new(), HashSet::new());
+    for (i, a) in cells.iter().enumerate().filter(|(_, c)| **c != '.') {
+        for (j, _) in cells.iter().enumerate().filter(|(j, b)| *j != i && *b == a) {
+            let ((ax, ay), (bx, by)) = (at(i), at(j));
+            let (dx, dy) = (bx - ax, by - ay);
+            if in_bounds((bx + dx, by + dy)) {
+                adjacent.insert((bx + dx, by + dy));
+            }
+            // Walking away from `a` through `b`, the other direction is covered by the swapped pair
+            let mut k = 0;
+            while in_bounds((ax + k * dx, ay + k * dy)) {
+                in_line.insert((ax + k * dx, ay + k * dy));
+                k += 1;
+            }
+        }
+    }
+
+    let mut input = String::new();
+    for row in cells.chunks(size) {
+        let _ = writeln!(input, "{}", row.iter().collect::<String>());
+    }
+
+    Generated {
+        part_one: Some(adjacent.len().into()),
+        part_two: Some(in_line.len().into()),
+        ..Generated::new(input)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::generate;
+    use crate::rng::Rng;
+    use crate::{for_day, Part};
+
+    #[test]
+    fn test_known_answers() {
+        for day in 1..=8 {
+            for seed in 0..5 {
+                let generated = generate(day, &mut Rng::new(seed), 20).unwrap();
+                let solution = for_day(day).unwrap();
+                let parsed = solution
+                    .parse_input(&generated.input)
+                    .unwrap_or_else(|e| panic!("Day {day} seed {seed}: {e}"));
+
+                for (part, known) in Part::BOTH
+                    .iter()
+                    .zip([generated.part_one, generated.part_two])
+                {
+                    let known = known
+                        .unwrap_or_else(|| panic!("Day {day} part {part} has no known answer"));
+                    let answer = solution.solve_part(&parsed, *part);
+                    assert_eq!(answer, known, "Day {day} part {part} seed {seed}");
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_seeded() {
+        let a = generate(3, &mut Rng::new(9), 10).unwrap();
+
+        assert_eq!(generate(3, &mut Rng::new(9), 10), Some(a.clone()));
+        assert_ne!(generate(3, &mut Rng::new(10), 10), Some(a));
+        assert_eq!(
+            generate(2, &mut Rng::new(1), 7)
+                .unwrap()
+                .input
+                .lines()
+                .count(),
+            7
+        );
+        assert!(generate(9, &mut Rng::new(1), 7).is_none());
+    }
+}
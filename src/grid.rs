@@ -0,0 +1,294 @@
+use std::fmt;
+use std::ops::{Index, IndexMut};
+
+use crate::math::{Direction, Vector2};
+use crate::parse;
+use crate::ParseError;
+
+/// A dense, rectangular grid stored in row major order.
+///
+/// Positions are `Vector2<isize>` with `x` increasing to the right and `y` increasing downwards,
+/// positions outside the grid are valid to query and yield `None`.
+#[derive(Clone, PartialEq, Eq, Hash)]
+pub struct Grid<T> {
+    cells: Vec<T>,
+    width: usize,
+    height: usize,
+}
+
+impl<T> Grid<T> {
+    /// **Note:** Panics if `width` is zero or the number of cells is not a multiple of `width`
+    pub fn from_cells(width: usize, cells: Vec<T>) -> Self {
+        assert!(
+            width > 0 && cells.len().is_multiple_of(width),
+            "Expected {} cells to fill rows of width {width}",
+            cells.len()
+        );
+
+        Self {
+            height: cells.len() / width,
+            cells,
+            width,
+        }
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn in_bounds(&self, position: Vector2<isize>) -> bool {
+        self.index_of(position).is_some()
+    }
+
+    pub fn get(&self, position: Vector2<isize>) -> Option<&T> {
+        self.index_of(position).map(|i| &self.cells[i])
+    }
+
+    pub fn get_mut(&mut self, position: Vector2<isize>) -> Option<&mut T> {
+        self.index_of(position).map(|i| &mut self.cells[i])
+    }
+
+    /// All positions in row major order.
+    pub fn positions(&self) -> impl Iterator<Item = Vector2<isize>> {
+        let width = self.width;
+
+        (0..self.cells.len()).map(move |i| Vector2::new((i % width) as isize, (i / width) as isize))
+    }
+
+    /// All cells with their positions in row major order.
+    pub fn iter(&self) -> impl Iterator<Item = (Vector2<isize>, &T)> {
+        self.positions().zip(self.cells.iter())
+    }
+
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> {
+        self.cells.chunks(self.width)
+    }
+
+    /// The in bounds orthogonal neighbors of `position`.
+    pub fn neighbors4(
+        &self,
+        position: Vector2<isize>,
+    ) -> impl Iterator<Item = Vector2<isize>> + '_ {
+        self.neighbors(position, &Direction::CARDINAL)
+    }
+
+    /// The in bounds orthogonal and diagonal neighbors of `position`.
+    pub fn neighbors8(
+        &self,
+        position: Vector2<isize>,
+    ) -> impl Iterator<Item = Vector2<isize>> + '_ {
+        self.neighbors(position, &Direction::ALL)
+    }
+
+    /// Walk from `start` in steps of `direction` until leaving the grid, `start` is included if
+    /// it's in bounds.
+    pub fn ray(&self, start: Vector2<isize>, direction: impl Into<Vector2<isize>>) -> Ray<'_, T> {
+        Ray {
+            grid: self,
+            current: start,
+            direction: direction.into(),
+        }
+    }
+
+    /// The first position, in row major order, whose cell matches `predicate`.
+    pub fn find(&self, predicate: impl Fn(&T) -> bool) -> Option<Vector2<isize>> {
+        self.iter().find(|(_, c)| predicate(c)).map(|(p, _)| p)
+    }
+
+    /// The first position, in row major order, whose cell is equal to `value`.
+    pub fn position(&self, value: &T) -> Option<Vector2<isize>>
+    where
+        T: PartialEq,
+    {
+        self.find(|c| c == value)
+    }
+
+    pub fn map<U>(&self, f: impl Fn(&T) -> U) -> Grid<U> {
+        Grid {
+            cells: self.cells.iter().map(f).collect(),
+            width: self.width,
+            height: self.height,
+        }
+    }
+
+    fn neighbors<'a>(
+        &'a self,
+        position: Vector2<isize>,
+        directions: &'static [Direction],
+    ) -> impl Iterator<Item = Vector2<isize>> + 'a {
+        directions
+            .iter()
+            .map(move |d| position + d.offset())
+            .filter(|p| self.in_bounds(*p))
+    }
+
+    fn index_of(&self, position: Vector2<isize>) -> Option<usize> {
+        let x = usize::try_from(position.x)
+            .ok()
+            .filter(|x| *x < self.width)?;
+        let y = usize::try_from(position.y)
+            .ok()
+            .filter(|y| *y < self.height)?;
+
+        Some(y * self.width + x)
+    }
+}
+
+impl<T: Clone> Grid<T> {
+    pub fn new(width: usize, height: usize, fill: T) -> Self {
+        Self::from_cells(width, vec![fill; width * height])
+    }
+}
+
+impl<T: TryFrom<char>> Grid<T> {
+    /// Parse a grid with one character per cell.
+    ///
+    /// Lines are trimmed and blank lines around the grid are skipped, all rows must have the same
+    /// width.
+    pub fn parse(input: &str) -> Result<Self, ParseError> {
+        parse::complete(input, parse::grid())
+    }
+}
+
+impl<T> Index<Vector2<isize>> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, position: Vector2<isize>) -> &Self::Output {
+        self.get(position)
+            .unwrap_or_else(|| panic!("Position {position:?} out of bounds"))
+    }
+}
+
+impl<T> IndexMut<Vector2<isize>> for Grid<T> {
+    fn index_mut(&mut self, position: Vector2<isize>) -> &mut Self::Output {
+        self.get_mut(position)
+            .unwrap_or_else(|| panic!("Position {position:?} out of bounds"))
+    }
+}
+
+impl<T: fmt::Display> fmt::Display for Grid<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for row in self.rows() {
+            for cell in row {
+                write!(f, "{cell}")?;
+            }
+            writeln!(f)?;
+        }
+
+        Ok(())
+    }
+}
+
+impl<T: fmt::Debug> fmt::Debug for Grid<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Grid")
+            .field("width", &self.width)
+            .field("height", &self.height)
+            .field("cells", &self.cells)
+            .finish()
+    }
+}
+
+/// Iterator over positions and cells along a straight line, see [`Grid::ray`].
+pub struct Ray<'a, T> {
+    grid: &'a Grid<T>,
+    current: Vector2<isize>,
+    direction: Vector2<isize>,
+}
+
+impl<'a, T> Iterator for Ray<'a, T> {
+    type Item = (Vector2<isize>, &'a T);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let position = self.current;
+        let cell = self.grid.get(position)?;
+        self.current = position + self.direction;
+
+        Some((position, cell))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Grid;
+    use crate::math::{Direction, Vector2};
+    use crate::ParseError;
+
+    const INPUT: &str = r#"
+#..
+.#.
+..X
+"#;
+
+    fn v(x: isize, y: isize) -> Vector2<isize> {
+        Vector2::new(x, y)
+    }
+
+    #[test]
+    fn test_parse() {
+        let grid = Grid::<char>::parse(INPUT).unwrap();
+
+        assert_eq!(grid.width(), 3);
+        assert_eq!(grid.height(), 3);
+        assert_eq!(grid.get(v(1, 1)), Some(&'#'));
+        assert_eq!(grid.get(v(3, 1)), None);
+        assert_eq!(grid.get(v(-1, 0)), None);
+        assert_eq!(grid.to_string(), INPUT.trim_start());
+    }
+
+    #[test]
+    fn test_parse_error() {
+        assert_eq!(
+            Grid::<char>::parse("##\n#\n").err(),
+            Some(ParseError::new(2, 1, "#", "row of width 2"))
+        );
+        assert!(Grid::<char>::parse("\n\n").is_err());
+
+        #[derive(Debug)]
+        struct Wall;
+        impl TryFrom<char> for Wall {
+            type Error = ();
+
+            fn try_from(value: char) -> Result<Self, Self::Error> {
+                (value == '#').then_some(Wall).ok_or(())
+            }
+        }
+
+        let error = Grid::<Wall>::parse("##\n#.").unwrap_err();
+        assert_eq!((error.line, error.column, error.text.as_str()), (2, 2, "."));
+    }
+
+    #[test]
+    fn test_neighbors() {
+        let grid = Grid::new(3, 2, 0);
+
+        assert_eq!(
+            grid.neighbors4(v(0, 0)).collect::<Vec<_>>(),
+            vec![v(1, 0), v(0, 1)]
+        );
+        assert_eq!(grid.neighbors8(v(1, 0)).count(), 5);
+        assert_eq!(grid.neighbors8(v(1, 1)).count(), 5);
+    }
+
+    #[test]
+    fn test_ray_and_find() {
+        let mut grid = Grid::<char>::parse(INPUT).unwrap();
+
+        let diagonal: String = grid
+            .ray(v(0, 0), Direction::DownRight)
+            .map(|(_, c)| *c)
+            .collect();
+        assert_eq!(diagonal, "##X");
+        assert_eq!(grid.ray(v(5, 5), v(1, 1)).count(), 0);
+
+        assert_eq!(grid.position(&'X'), Some(v(2, 2)));
+        assert_eq!(grid.find(|c| *c == '#'), Some(v(0, 0)));
+
+        grid[v(2, 2)] = '.';
+        assert_eq!(grid.position(&'X'), None);
+    }
+}
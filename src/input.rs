@@ -0,0 +1,184 @@
+use std::env;
+use std::fmt;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+/// Environment variable overriding the input directory.
+pub const INPUT_DIR_ENV: &str = "AOC_INPUT_DIR";
+/// Environment variable selecting a per-user input set within the input directory.
+pub const USER_ENV: &str = "AOC_USER";
+pub const DEFAULT_INPUT_DIR: &str = "inputs";
+/// The path that reads input from stdin instead of a file.
+pub const STDIN: &str = "-";
+
+/// Where puzzle inputs are read from.
+///
+/// Inputs are named `dayNN.txt` and live directly in the input directory, or in a sub directory
+/// per user when a user is selected, e.g. `inputs/alice/day01.txt`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Inputs {
+    dir: PathBuf,
+    user: Option<String>,
+}
+
+impl Inputs {
+    pub fn new(dir: impl Into<PathBuf>) -> Self {
+        Self {
+            dir: dir.into(),
+            user: None,
+        }
+    }
+
+    /// Inputs configured by [`INPUT_DIR_ENV`] and [`USER_ENV`], defaulting to
+    /// [`DEFAULT_INPUT_DIR`] without a user.
+    pub fn from_env() -> Self {
+        let dir = env::var_os(INPUT_DIR_ENV)
+            .filter(|d| !d.is_empty())
+            .map(PathBuf::from)
+            .unwrap_or_else(|| PathBuf::from(DEFAULT_INPUT_DIR));
+        let user = env::var(USER_ENV).ok().filter(|u| !u.is_empty());
+
+        Self { dir, user }
+    }
+
+    pub fn with_dir(self, dir: impl Into<PathBuf>) -> Self {
+        Self {
+            dir: dir.into(),
+            ..self
+        }
+    }
+
+    pub fn with_user(self, user: impl Into<String>) -> Self {
+        Self {
+            user: Some(user.into()),
+            ..self
+        }
+    }
+
+    /// The directory holding the selected input set.
+    pub fn dir(&self) -> PathBuf {
+        match &self.user {
+            Some(user) => self.dir.join(user),
+            None => self.dir.clone(),
+        }
+    }
+
+    pub fn path(&self, day: u8) -> PathBuf {
+        self.dir().join(format!("day{day:02}.txt"))
+    }
+
+    pub fn exists(&self, day: u8) -> bool {
+        self.path(day).is_file()
+    }
+
+    pub fn load(&self, day: u8) -> Result<String, InputError> {
+        read(self.path(day))
+    }
+}
+
+impl Default for Inputs {
+    fn default() -> Self {
+        Self::new(DEFAULT_INPUT_DIR)
+    }
+}
+
+#[derive(Debug)]
+pub enum InputError {
+    /// There is no input at `path`.
+    Missing { path: PathBuf },
+    /// The input at `path` exists but could not be read.
+    Io { path: PathBuf, source: io::Error },
+}
+
+impl InputError {
+    pub fn is_missing(&self) -> bool {
+        matches!(self, Self::Missing { .. })
+    }
+
+    pub fn path(&self) -> &Path {
+        match self {
+            Self::Missing { path } | Self::Io { path, .. } => path,
+        }
+    }
+}
+
+impl fmt::Display for InputError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Missing { path } => write!(f, "input missing, expected at {}", path.display()),
+            Self::Io { path, source } => {
+                write!(f, "unable to read input {}: {source}", path.display())
+            }
+        }
+    }
+}
+
+impl std::error::Error for InputError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::Missing { .. } => None,
+            Self::Io { source, .. } => Some(source),
+        }
+    }
+}
+
+/// Read the input at `path`, or all of stdin if `path` is [`STDIN`].
+pub fn read(path: impl AsRef<Path>) -> Result<String, InputError> {
+    let path = path.as_ref();
+
+    if is_stdin(path) {
+        return io::read_to_string(io::stdin()).map_err(|source| InputError::Io {
+            path: path.to_path_buf(),
+            source,
+        });
+    }
+
+    fs::read_to_string(path).map_err(|source| {
+        let path = path.to_path_buf();
+
+        if source.kind() == io::ErrorKind::NotFound {
+            InputError::Missing { path }
+        } else {
+            InputError::Io { path, source }
+        }
+    })
+}
+
+pub fn is_stdin(path: impl AsRef<Path>) -> bool {
+    path.as_ref() == Path::new(STDIN)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+    use std::path::PathBuf;
+
+    use super::Inputs;
+
+    #[test]
+    fn test_paths() {
+        let inputs = Inputs::new("inputs");
+        assert_eq!(inputs.path(3), PathBuf::from("inputs/day03.txt"));
+
+        let inputs = inputs.with_user("alice");
+        assert_eq!(inputs.dir(), PathBuf::from("inputs/alice"));
+        assert_eq!(inputs.path(12), PathBuf::from("inputs/alice/day12.txt"));
+    }
+
+    #[test]
+    fn test_load() {
+        let dir = std::env::temp_dir().join(format!("aoc-input-test-{}", std::process::id()));
+        fs::create_dir_all(dir.join("bob")).unwrap();
+        fs::write(dir.join("bob/day01.txt"), "3   4\n").unwrap();
+
+        let inputs = Inputs::new(&dir).with_user("bob");
+        assert_eq!(inputs.load(1).unwrap(), "3   4\n");
+
+        let error = inputs.load(2).unwrap_err();
+        assert!(error.is_missing());
+        assert_eq!(error.path(), dir.join("bob/day02.txt"));
+
+        fs::remove_dir_all(dir).unwrap();
+    }
+}
@@ -0,0 +1,871 @@
+use std::env;
+use std::fs;
+use std::io;
+use std::panic::{self, AssertUnwindSafe};
+use std::path::{Path, PathBuf};
+use std::process::ExitCode;
+
+use advent_of_rust_2024::answers::{self, Answers};
+use advent_of_rust_2024::bench::{self, format_duration, time};
+use advent_of_rust_2024::day06::Day06;
+use advent_of_rust_2024::fetch::{Curl, Downloader, Fetched, Session};
+use advent_of_rust_2024::gen;
+use advent_of_rust_2024::input::{self, Inputs};
+use advent_of_rust_2024::math::Vector2;
+use advent_of_rust_2024::profile;
+use advent_of_rust_2024::rng::Rng;
+use advent_of_rust_2024::{for_day, solutions, stream, Answer, Part, Solution};
+
+const USAGE: &str = "\
+Usage: aoc [INPUT OPTIONS] --day <N> [--part <1|2>] [--input <PATH>] [--record]
+       aoc [INPUT OPTIONS] --all
+       aoc [INPUT OPTIONS] bench [--day <N>] [--iterations <N>] [--warmup <N>] [--format <markdown|json>]
+       aoc [INPUT OPTIONS] profile [--day <N>]
+       aoc [INPUT OPTIONS] fetch --day <N>
+       aoc gen --day <N> [--size <N>] [--seed <N>]
+       aoc [INPUT OPTIONS] patrol --out <DIR> [--every <N>] [--scale <N>] [--obstruction <X,Y>]
+
+Input options:
+    --input-dir <DIR>    Directory with dayNN.txt inputs, defaults to $AOC_INPUT_DIR or inputs
+    --user <NAME>        Use the input set in <DIR>/<NAME>, defaults to $AOC_USER
+
+Options:
+    -d, --day <N>        Day to run, 1 to 24
+    -p, --part <1|2>     Only run one part, both parts are run by default
+    -i, --input <PATH>   Puzzle input, overrides the input directory, `-` reads stdin. Days 1, 2, 3
+                         and 7 stream stdin with bounded memory when `--part` is given
+    -r, --record         Record the answers as verified in answers.toml at the crate root
+    -a, --all            Run every day that has an input file
+    -h, --help           Print this help
+
+Bench options:
+    -d, --day <N>        Only benchmark one day, every day with an input file by default
+    --iterations <N>     Timed runs of each phase, defaults to 20
+    --warmup <N>         Untimed runs before measuring, defaults to 3
+    --format <FORMAT>    Output as a `markdown` table (default) or `json`
+
+Profile options:
+    -d, --day <N>        Only profile one day, every day with an input file by default. Runs parsing
+                         and each part once, allocations are counted with the `count-alloc` feature
+
+Fetch options:
+    -d, --day <N>        Download the input for a day into the input directory, unless it is
+                         already there. Needs curl on PATH and the session cookie of a logged in
+                         browser in $AOC_SESSION or ~/.config/aoc/session
+
+Gen options:
+    -d, --day <N>        Day to generate an input for, 1 to 8
+    --size <N>           Lines, or width and height for grids, defaults to 1000
+    --seed <N>           Seed of the random generator, defaults to 0
+
+Patrol options:
+    --out <DIR>          Write the day 6 guard's patrol as PPM frames to this directory
+    --every <N>          Keep every Nth step and the last one, defaults to 50
+    --scale <N>          Pixels per cell, defaults to 4
+    --obstruction <X,Y>  Add an obstruction, highlighted in red";
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+enum Format {
+    Markdown,
+    Json,
+}
+
+#[derive(Debug, PartialEq, Eq)]
+struct Args {
+    command: Command,
+    input_dir: Option<PathBuf>,
+    user: Option<String>,
+}
+
+impl Args {
+    fn inputs(&self) -> Inputs {
+        let mut inputs = Inputs::from_env();
+        if let Some(dir) = &self.input_dir {
+            inputs = inputs.with_dir(dir);
+        }
+        if let Some(user) = &self.user {
+            inputs = inputs.with_user(user);
+        }
+
+        inputs
+    }
+}
+
+#[derive(Debug, PartialEq, Eq)]
+enum Command {
+    Help,
+    All,
+    Day {
+        day: u8,
+        part: Option<Part>,
+        input: Option<PathBuf>,
+        record: bool,
+    },
+    Bench {
+        day: Option<u8>,
+        config: bench::Config,
+        format: Format,
+    },
+    Profile {
+        day: Option<u8>,
+    },
+    Fetch {
+        day: u8,
+    },
+    Gen {
+        day: u8,
+        size: usize,
+        seed: u64,
+    },
+    Patrol {
+        out: PathBuf,
+        every: usize,
+        scale: usize,
+        obstruction: Option<Vector2<isize>>,
+    },
+}
+
+fn main() -> ExitCode {
+    let args = match parse_args(env::args().skip(1)) {
+        Ok(args) => args,
+        Err(message) => {
+            eprintln!("error: {message}\n\n{USAGE}");
+            return ExitCode::from(2);
+        }
+    };
+    let inputs = args.inputs();
+
+    let success = match args.command {
+        Command::Help => {
+            println!("{USAGE}");
+            true
+        }
+        Command::All => solutions().fold(true, |success, (day, _)| {
+            if !inputs.exists(day) {
+                println!(
+                    "Day {day:02}: skipped, no input at {}",
+                    inputs.path(day).display()
+                );
+                return success;
+            }
+
+            run_day(day, None, &inputs.path(day), false) && success
+        }),
+        Command::Day {
+            day,
+            part,
+            input,
+            record,
+        } => {
+            let path = input.unwrap_or_else(|| inputs.path(day));
+
+            run_day(day, part, &path, record)
+        }
+        Command::Bench {
+            day,
+            config,
+            format,
+        } => run_bench(&inputs, day, &config, format),
+        Command::Profile { day } => run_profile(&inputs, day),
+        Command::Fetch { day } => run_fetch(&inputs, day),
+        Command::Gen { day, size, seed } => run_gen(day, size, seed),
+        Command::Patrol {
+            out,
+            every,
+            scale,
+            obstruction,
+        } => run_patrol(&inputs, &out, every, scale, obstruction),
+    };
+
+    if success {
+        ExitCode::SUCCESS
+    } else {
+        ExitCode::FAILURE
+    }
+}
+
+fn parse_args(mut args: impl Iterator<Item = String>) -> Result<Args, String> {
+    let mut input_dir = None;
+    let mut user = None;
+    let mut rest = vec![];
+
+    while let Some(arg) = args.next() {
+        let mut value = |name: &str| args.next().ok_or(format!("missing value for `{name}`"));
+
+        match arg.as_str() {
+            "--input-dir" => input_dir = Some(PathBuf::from(value(&arg)?)),
+            "--user" => user = Some(value(&arg)?),
+            _ => rest.push(arg),
+        }
+    }
+
+    let command = match rest.first().map(String::as_str) {
+        Some("bench") => parse_bench_args(rest.into_iter().skip(1))?,
+        Some("profile") => parse_profile_args(rest.into_iter().skip(1))?,
+        Some("fetch") => parse_fetch_args(rest.into_iter().skip(1))?,
+        Some("gen") => parse_gen_args(rest.into_iter().skip(1))?,
+        Some("patrol") => parse_patrol_args(rest.into_iter().skip(1))?,
+        _ => parse_command_args(rest.into_iter())?,
+    };
+
+    Ok(Args {
+        command,
+        input_dir,
+        user,
+    })
+}
+
+fn parse_command_args(mut args: impl Iterator<Item = String>) -> Result<Command, String> {
+    let mut all = false;
+    let mut day = None;
+    let mut part = None;
+    let mut input = None;
+    let mut record = false;
+
+    while let Some(arg) = args.next() {
+        let mut value = |name: &str| args.next().ok_or(format!("missing value for `{name}`"));
+
+        match arg.as_str() {
+            "-h" | "--help" => return Ok(Command::Help),
+            "-a" | "--all" => all = true,
+            "-d" | "--day" => day = Some(parse_day(&value(&arg)?)?),
+            "-p" | "--part" => {
+                let value = value(&arg)?;
+                let parsed = match value.as_str() {
+                    "1" => Part::One,
+                    "2" => Part::Two,
+                    _ => return Err(format!("invalid part `{value}`, expected 1 or 2")),
+                };
+                part = Some(parsed);
+            }
+            "-i" | "--input" => input = Some(PathBuf::from(value(&arg)?)),
+            "-r" | "--record" => record = true,
+            _ => return Err(format!("unexpected argument `{arg}`")),
+        }
+    }
+
+    match (all, day) {
+        (true, None) if part.is_none() && input.is_none() && !record => Ok(Command::All),
+        (true, _) => Err("`--all` cannot be combined with other options".to_owned()),
+        (false, Some(day)) => Ok(Command::Day {
+            day,
+            part,
+            input,
+            record,
+        }),
+        (false, None) => Err("either `--day` or `--all` is required".to_owned()),
+    }
+}
+
+fn parse_bench_args(mut args: impl Iterator<Item = String>) -> Result<Command, String> {
+    let mut day = None;
+    let mut config = bench::Config::default();
+    let mut format = Format::Markdown;
+
+    while let Some(arg) = args.next() {
+        let mut value = |name: &str| args.next().ok_or(format!("missing value for `{name}`"));
+
+        match arg.as_str() {
+            "-h" | "--help" => return Ok(Command::Help),
+            "-d" | "--day" => day = Some(parse_day(&value(&arg)?)?),
+            "--iterations" => config.iterations = parse_count(&value(&arg)?)?,
+            "--warmup" => config.warmup = parse_count(&value(&arg)?)?,
+            "--format" => {
+                format = match value(&arg)?.as_str() {
+                    "markdown" => Format::Markdown,
+                    "json" => Format::Json,
+                    other => return Err(format!("invalid format `{other}`")),
+                }
+            }
+            _ => return Err(format!("unexpected argument `{arg}`")),
+        }
+    }
+
+    if config.iterations == 0 {
+        return Err("`--iterations` must be at least 1".to_owned());
+    }
+
+    Ok(Command::Bench {
+        day,
+        config,
+        format,
+    })
+}
+
+fn parse_profile_args(mut args: impl Iterator<Item = String>) -> Result<Command, String> {
+    let mut day = None;
+
+    while let Some(arg) = args.next() {
+        let mut value = |name: &str| args.next().ok_or(format!("missing value for `{name}`"));
+
+        match arg.as_str() {
+            "-h" | "--help" => return Ok(Command::Help),
+            "-d" | "--day" => day = Some(parse_day(&value(&arg)?)?),
+            _ => return Err(format!("unexpected argument `{arg}`")),
+        }
+    }
+
+    Ok(Command::Profile { day })
+}
+
+fn parse_fetch_args(mut args: impl Iterator<Item = String>) -> Result<Command, String> {
+    let mut day = None;
+
+    while let Some(arg) = args.next() {
+        let mut value = |name: &str| args.next().ok_or(format!("missing value for `{name}`"));
+
+        match arg.as_str() {
+            "-h" | "--help" => return Ok(Command::Help),
+            "-d" | "--day" => day = Some(parse_day(&value(&arg)?)?),
+            _ => return Err(format!("unexpected argument `{arg}`")),
+        }
+    }
+
+    day.map(|day| Command::Fetch { day })
+        .ok_or("`--day` is required".to_owned())
+}
+
+fn parse_gen_args(mut args: impl Iterator<Item = String>) -> Result<Command, String> {
+    let mut day = None;
+    let mut size = 1000;
+    let mut seed = 0;
+
+    while let Some(arg) = args.next() {
+        let mut value = |name: &str| args.next().ok_or(format!("missing value for `{name}`"));
+
+        match arg.as_str() {
+            "-h" | "--help" => return Ok(Command::Help),
+            "-d" | "--day" => day = Some(parse_day(&value(&arg)?)?),
+            "--size" => size = parse_count(&value(&arg)?)?,
+            "--seed" => {
+                let value = value(&arg)?;
+                seed = value
+                    .parse()
+                    .map_err(|_| format!("invalid seed `{value}`"))?;
+            }
+            _ => return Err(format!("unexpected argument `{arg}`")),
+        }
+    }
+
+    match day {
+        Some(day) if size > 0 => Ok(Command::Gen { day, size, seed }),
+        Some(_) => Err("`--size` must be at least 1".to_owned()),
+        None => Err("`--day` is required".to_owned()),
+    }
+}
+
+fn parse_patrol_args(mut args: impl Iterator<Item = String>) -> Result<Command, String> {
+    let mut out = None;
+    let mut every = 50;
+    let mut scale = 4;
+    let mut obstruction = None;
+
+    while let Some(arg) = args.next() {
+        let mut value = |name: &str| args.next().ok_or(format!("missing value for `{name}`"));
+
+        match arg.as_str() {
+            "-h" | "--help" => return Ok(Command::Help),
+            "--out" => out = Some(PathBuf::from(value(&arg)?)),
+            "--every" => every = parse_count(&value(&arg)?)?,
+            "--scale" => scale = parse_count(&value(&arg)?)?,
+            "--obstruction" => {
+                let value = value(&arg)?;
+                let position = value
+                    .split_once(',')
+                    .and_then(|(x, y)| Some(Vector2::new(x.parse().ok()?, y.parse().ok()?)))
+                    .ok_or(format!("invalid obstruction `{value}`, expected X,Y"))?;
+                obstruction = Some(position);
+            }
+            _ => return Err(format!("unexpected argument `{arg}`")),
+        }
+    }
+
+    if every == 0 || scale == 0 {
+        return Err("`--every` and `--scale` must be at least 1".to_owned());
+    }
+
+    match out {
+        Some(out) => Ok(Command::Patrol {
+            out,
+            every,
+            scale,
+            obstruction,
+        }),
+        None => Err("`--out` is required".to_owned()),
+    }
+}
+
+fn parse_day(value: &str) -> Result<u8, String> {
+    value
+        .parse::<u8>()
+        .ok()
+        .filter(|d| (1..=24).contains(d))
+        .ok_or(format!("invalid day `{value}`, expected 1 to 24"))
+}
+
+fn parse_count(value: &str) -> Result<usize, String> {
+    value
+        .parse()
+        .map_err(|_| format!("invalid count `{value}`"))
+}
+
+/// Run one or both parts of `day`, printing the answers and optionally recording them.
+///
+/// Returns whether all requested parts ran successfully.
+fn run_day(day: u8, part: Option<Part>, path: &Path, record: bool) -> bool {
+    let solution = for_day(day).expect("Days are validated when parsing arguments");
+
+    if let Some(part) = part.filter(|_| input::is_stdin(path)) {
+        if let Some(solver) = stream::for_part(day, part) {
+            return run_streaming(day, part, solver, record);
+        }
+    }
+
+    let input = match input::read(path) {
+        Ok(input) => input,
+        Err(e) => {
+            eprintln!("Day {day:02}: {e}");
+            return false;
+        }
+    };
+
+    let parsed =
+        match panic::catch_unwind(AssertUnwindSafe(|| time(|| solution.parse_input(&input)))) {
+            Ok((Ok(parsed), duration)) => {
+                println!("Day {day:02} parse: ({})", format_duration(duration));
+                parsed
+            }
+            Ok((Err(e), _)) => {
+                eprintln!("Day {day:02}: invalid input {}: {e}", path.display());
+                return false;
+            }
+            Err(_) => {
+                eprintln!("Day {day:02}: parser panicked");
+                return false;
+            }
+        };
+
+    let parts = match part {
+        Some(part) => vec![part],
+        None => Part::BOTH.to_vec(),
+    };
+
+    let mut success = true;
+    let mut solved = vec![];
+    for part in parts {
+        let result = panic::catch_unwind(AssertUnwindSafe(|| {
+            time(|| solution.solve_part(&parsed, part))
+        }));
+
+        match result {
+            Ok((answer, duration)) => {
+                println!(
+                    "Day {day:02} part {part}: {answer} ({})",
+                    format_duration(duration)
+                );
+                solved.push((part, answer));
+            }
+            Err(_) => {
+                eprintln!("Day {day:02} part {part}: solver panicked");
+                success = false;
+            }
+        }
+    }
+
+    if record {
+        success &= record_answers(day, solved);
+    }
+
+    success
+}
+
+/// Solve one `part` of `day` while reading stdin, without holding all of it in memory.
+///
+/// Returns whether the part ran successfully.
+fn run_streaming(day: u8, part: Part, solver: stream::Solver, record: bool) -> bool {
+    let result = panic::catch_unwind(AssertUnwindSafe(|| {
+        time(|| solver(&mut io::stdin().lock()))
+    }));
+
+    match result {
+        Ok((Ok(answer), duration)) => {
+            println!(
+                "Day {day:02} part {part}: {answer} ({})",
+                format_duration(duration)
+            );
+
+            !record || record_answers(day, vec![(part, answer)])
+        }
+        Ok((Err(e), _)) => {
+            eprintln!("Day {day:02}: invalid input from stdin: {e}");
+            false
+        }
+        Err(_) => {
+            eprintln!("Day {day:02} part {part}: solver panicked");
+            false
+        }
+    }
+}
+
+/// Record solved answers for `day` in the answers file.
+///
+/// Returns whether the answers were recorded.
+fn record_answers(day: u8, solved: Vec<(Part, Answer)>) -> bool {
+    let path = answers::path();
+    let mut answers = match Answers::load(&path) {
+        Ok(answers) => answers,
+        Err(e) => {
+            eprintln!("Unable to load {}: {e}", path.display());
+            return false;
+        }
+    };
+
+    for (part, answer) in solved {
+        if !answer.is_solved() {
+            println!("Day {day:02} part {part}: not recording unsolved answer");
+            continue;
+        }
+
+        answers.record(day, part, answer);
+    }
+
+    match answers.save(&path) {
+        Ok(()) => {
+            println!("Recorded answers for day {day:02} in {}", path.display());
+            true
+        }
+        Err(e) => {
+            eprintln!("Unable to save {}: {e}", path.display());
+            false
+        }
+    }
+}
+
+/// Benchmark `day`, or every day with an input, and print the results in `format`.
+///
+/// Returns whether all benchmarks ran successfully.
+fn run_bench(inputs: &Inputs, day: Option<u8>, config: &bench::Config, format: Format) -> bool {
+    let mut success = true;
+    let mut reports = vec![];
+    for day in selected_days(inputs, day) {
+        let solution = for_day(day).expect("Days are validated when parsing arguments");
+        let input = match inputs.load(day) {
+            Ok(input) => input,
+            Err(e) => {
+                eprintln!("Day {day:02}: {e}");
+                success = false;
+                continue;
+            }
+        };
+
+        match panic::catch_unwind(AssertUnwindSafe(|| {
+            bench::bench(day, solution, &input, config)
+        })) {
+            Ok(Ok(report)) => reports.push(report),
+            Ok(Err(e)) => {
+                eprintln!(
+                    "Day {day:02}: invalid input {}: {e}",
+                    inputs.path(day).display()
+                );
+                success = false;
+            }
+            Err(_) => {
+                eprintln!("Day {day:02}: solver panicked");
+                success = false;
+            }
+        }
+    }
+
+    match format {
+        Format::Markdown => print!("{}", bench::to_markdown(&reports)),
+        Format::Json => print!("{}", bench::to_json(&reports)),
+    }
+
+    success
+}
+
+/// Profile `day`, or every day with an input, and print where the time and memory goes.
+///
+/// Returns whether all days ran successfully.
+fn run_profile(inputs: &Inputs, day: Option<u8>) -> bool {
+    if !profile::counts_allocations() {
+        eprintln!("Allocations are only counted with the `count-alloc` feature");
+    }
+
+    let mut success = true;
+    let mut profiles = vec![];
+    for day in selected_days(inputs, day) {
+        let solution = for_day(day).expect("Days are validated when parsing arguments");
+        let input = match inputs.load(day) {
+            Ok(input) => input,
+            Err(e) => {
+                eprintln!("Day {day:02}: {e}");
+                success = false;
+                continue;
+            }
+        };
+
+        match panic::catch_unwind(AssertUnwindSafe(|| profile::profile(day, solution, &input))) {
+            Ok(Ok(profile)) => profiles.push(profile),
+            Ok(Err(e)) => {
+                eprintln!(
+                    "Day {day:02}: invalid input {}: {e}",
+                    inputs.path(day).display()
+                );
+                success = false;
+            }
+            Err(_) => {
+                eprintln!("Day {day:02}: solver panicked");
+                success = false;
+            }
+        }
+    }
+
+    print!("{}", profile::to_markdown(&profiles));
+
+    success
+}
+
+/// `day` if given, otherwise every day with an input file.
+fn selected_days(inputs: &Inputs, day: Option<u8>) -> Vec<u8> {
+    match day {
+        Some(day) => vec![day],
+        None => solutions()
+            .map(|(day, _)| day)
+            .filter(|day| inputs.exists(*day))
+            .collect(),
+    }
+}
+
+/// Download the input for `day` unless it is already in the input directory.
+///
+/// Returns whether the input is available afterwards.
+fn run_fetch(inputs: &Inputs, day: u8) -> bool {
+    // Cached inputs don't need a session
+    if inputs.exists(day) {
+        println!("Day {day:02}: already at {}", inputs.path(day).display());
+        return true;
+    }
+
+    let result =
+        Session::load().and_then(|session| Downloader::new(Curl, session).fetch(inputs, day));
+    match result {
+        Ok(Fetched::Downloaded(path)) => {
+            println!("Day {day:02}: downloaded to {}", path.display());
+            true
+        }
+        Ok(Fetched::Cached(path)) => {
+            println!("Day {day:02}: already at {}", path.display());
+            true
+        }
+        Err(e) => {
+            eprintln!("Day {day:02}: {e}");
+            false
+        }
+    }
+}
+
+/// Print a generated input for `day` to stdout and the answers known from generating it to
+/// stderr, so the input can be piped into another run.
+///
+/// Returns whether `day` has a generator.
+fn run_gen(day: u8, size: usize, seed: u64) -> bool {
+    let Some(generated) = gen::generate(day, &mut Rng::new(seed), size) else {
+        eprintln!("Day {day:02}: no generator");
+        return false;
+    };
+
+    print!("{}", generated.input);
+    for (part, known) in Part::BOTH
+        .iter()
+        .zip([generated.part_one, generated.part_two])
+    {
+        if let Some(answer) = known {
+            eprintln!("Day {day:02} part {part}: {answer} expected");
+        }
+    }
+
+    true
+}
+
+/// Write the frames of the day 6 guard's patrol to `out`.
+///
+/// Returns whether the frames were written.
+fn run_patrol(
+    inputs: &Inputs,
+    out: &Path,
+    every: usize,
+    scale: usize,
+    obstruction: Option<Vector2<isize>>,
+) -> bool {
+    let lab = match inputs.load(6).map(|input| Day06::parse(&input)) {
+        Ok(Ok(lab)) => lab,
+        Ok(Err(e)) => {
+            eprintln!("Day 06: invalid input {}: {e}", inputs.path(6).display());
+            return false;
+        }
+        Err(e) => {
+            eprintln!("Day 06: {e}");
+            return false;
+        }
+    };
+    if let Some(at) = obstruction.filter(|at| !lab.contains(*at)) {
+        eprintln!("Day 06: obstruction {},{} is outside the lab", at.x, at.y);
+        return false;
+    }
+
+    let written =
+        fs::create_dir_all(out).and_then(|_| lab.write_patrol(out, obstruction, every, scale));
+    match written {
+        Ok(paths) => {
+            println!("Day 06: wrote {} frames to {}", paths.len(), out.display());
+            true
+        }
+        Err(e) => {
+            eprintln!("Day 06: unable to write frames to {}: {e}", out.display());
+            false
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::PathBuf;
+
+    use advent_of_rust_2024::bench::Config;
+    use advent_of_rust_2024::math::Vector2;
+    use advent_of_rust_2024::Part;
+
+    use super::{parse_args, Args, Command, Format};
+
+    fn parse(args: &[&str]) -> Result<Command, String> {
+        parse_args(args.iter().map(|a| a.to_string())).map(|a| a.command)
+    }
+
+    #[test]
+    fn test_parse_args() {
+        assert_eq!(
+            parse(&["--day", "7", "--part", "2", "--input", "example.txt"]),
+            Ok(Command::Day {
+                day: 7,
+                part: Some(Part::Two),
+                input: Some(PathBuf::from("example.txt")),
+                record: false,
+            })
+        );
+        assert_eq!(
+            parse(&["-d", "1"]),
+            Ok(Command::Day {
+                day: 1,
+                part: None,
+                input: None,
+                record: false,
+            })
+        );
+        assert_eq!(
+            parse(&["-d", "3", "-p", "1", "-i", "-"]),
+            Ok(Command::Day {
+                day: 3,
+                part: Some(Part::One),
+                input: Some(PathBuf::from("-")),
+                record: false,
+            })
+        );
+        assert_eq!(parse(&["--all"]), Ok(Command::All));
+        assert_eq!(
+            parse(&["patrol", "--out", "frames", "--obstruction", "3,6"]),
+            Ok(Command::Patrol {
+                out: PathBuf::from("frames"),
+                every: 50,
+                scale: 4,
+                obstruction: Some(Vector2::new(3, 6)),
+            })
+        );
+        assert_eq!(parse(&["profile"]), Ok(Command::Profile { day: None }));
+        assert_eq!(parse(&["fetch", "-d", "3"]), Ok(Command::Fetch { day: 3 }));
+        assert_eq!(
+            parse(&["profile", "-d", "8"]),
+            Ok(Command::Profile { day: Some(8) })
+        );
+        assert_eq!(
+            parse(&["gen", "-d", "5", "--seed", "42"]),
+            Ok(Command::Gen {
+                day: 5,
+                size: 1000,
+                seed: 42,
+            })
+        );
+        assert_eq!(
+            parse(&["--day", "9", "--record"]),
+            Ok(Command::Day {
+                day: 9,
+                part: None,
+                input: None,
+                record: true,
+            })
+        );
+        assert_eq!(
+            parse(&[
+                "bench",
+                "--day",
+                "3",
+                "--iterations",
+                "5",
+                "--format",
+                "json"
+            ]),
+            Ok(Command::Bench {
+                day: Some(3),
+                config: Config {
+                    warmup: Config::default().warmup,
+                    iterations: 5,
+                },
+                format: Format::Json,
+            })
+        );
+    }
+
+    #[test]
+    fn test_parse_input_args() {
+        assert_eq!(
+            parse_args(
+                ["--input-dir", "/tmp/aoc", "bench", "--user", "alice"]
+                    .iter()
+                    .map(|a| a.to_string())
+            ),
+            Ok(Args {
+                command: Command::Bench {
+                    day: None,
+                    config: Config::default(),
+                    format: Format::Markdown,
+                },
+                input_dir: Some(PathBuf::from("/tmp/aoc")),
+                user: Some("alice".to_owned()),
+            })
+        );
+    }
+
+    #[test]
+    fn test_parse_args_invalid() {
+        assert!(parse(&[]).is_err());
+        assert!(parse(&["--day", "25"]).is_err());
+        assert!(parse(&["--day", "1", "--part", "3"]).is_err());
+        assert!(parse(&["--day"]).is_err());
+        assert!(parse(&["--all", "--day", "1"]).is_err());
+        assert!(parse(&["--all", "--record"]).is_err());
+        assert!(parse(&["--bogus"]).is_err());
+        assert!(parse(&["--day", "1", "--input-dir"]).is_err());
+        assert!(parse(&["bench", "--iterations", "0"]).is_err());
+        assert!(parse(&["bench", "--format", "xml"]).is_err());
+        assert!(parse(&["profile", "--day", "0"]).is_err());
+        assert!(parse(&["profile", "--iterations", "3"]).is_err());
+        assert!(parse(&["fetch"]).is_err());
+        assert!(parse(&["fetch", "--day", "26"]).is_err());
+        assert!(parse(&["gen"]).is_err());
+        assert!(parse(&["patrol"]).is_err());
+        assert!(parse(&["patrol", "--out", "f", "--obstruction", "3"]).is_err());
+        assert!(parse(&["patrol", "--out", "f", "--every", "0"]).is_err());
+        assert!(parse(&["gen", "--day", "2", "--size", "0"]).is_err());
+        assert!(parse(&["gen", "--day", "2", "--seed", "-1"]).is_err());
+    }
+}
@@ -1,6 +1,10 @@
 use std::fmt;
-use std::hash::Hash;
-use std::ops::{Add, Mul, Neg, Sub};
+use std::ops::{
+    Add, AddAssign, Div, DivAssign, Mul, MulAssign, Neg, Rem, RemAssign, Sub, SubAssign,
+};
+
+pub mod linalg;
+pub mod num;
 
 #[derive(Copy, Clone, Hash, PartialEq, Eq)]
 pub struct Vector2<T> {
@@ -8,18 +12,30 @@ pub struct Vector2<T> {
     pub y: T,
 }
 
+#[derive(Copy, Clone, Hash, PartialEq, Eq)]
+pub struct Vector3<T> {
+    pub x: T,
+    pub y: T,
+    pub z: T,
+}
+
 impl<T> Vector2<T> {
     pub const fn new(x: T, y: T) -> Self {
         Self { x, y }
     }
+
+    pub fn map<U>(self, f: impl Fn(T) -> U) -> Vector2<U> {
+        Vector2::new(f(self.x), f(self.y))
+    }
 }
 
-impl<T: Default> Default for Vector2<T> {
-    fn default() -> Self {
-        Self {
-            x: T::default(),
-            y: T::default(),
-        }
+impl<T> Vector3<T> {
+    pub const fn new(x: T, y: T, z: T) -> Self {
+        Self { x, y, z }
+    }
+
+    pub fn map<U>(self, f: impl Fn(T) -> U) -> Vector3<U> {
+        Vector3::new(f(self.x), f(self.y), f(self.z))
     }
 }
 
@@ -29,46 +45,143 @@ impl<T: fmt::Debug> fmt::Debug for Vector2<T> {
     }
 }
 
-impl<T: Add<Output = T>> Add for Vector2<T> {
-    type Output = Vector2<T>;
-
-    fn add(self, rhs: Vector2<T>) -> Self::Output {
-        Vector2::<T> {
-            x: self.x + rhs.x,
-            y: self.y + rhs.y,
-        }
+impl<T: fmt::Debug> fmt::Debug for Vector3<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "<Vector3 x={:?} y={:?} z={:?} >", self.x, self.y, self.z)
     }
 }
 
-impl<T: Sub<Output = T>> Sub for Vector2<T> {
-    type Output = Vector2<T>;
+/// Implements the component-wise and scalar operators, the distances, and the conversions
+/// shared by all vector types.
+macro_rules! define_vector {
+    ($V:ident { $($c:ident),+ }) => {
+        impl<T: Default> Default for $V<T> {
+            fn default() -> Self {
+                Self {
+                    $($c: T::default()),+
+                }
+            }
+        }
+
+        define_vector!(@op $V { $($c),+ } Add add AddAssign add_assign);
+        define_vector!(@op $V { $($c),+ } Sub sub SubAssign sub_assign);
+        define_vector!(@op $V { $($c),+ } Mul mul MulAssign mul_assign);
+        define_vector!(@op $V { $($c),+ } Div div DivAssign div_assign);
+        define_vector!(@op $V { $($c),+ } Rem rem RemAssign rem_assign);
+        define_vector!(@scalar $V { $($c),+ } Mul mul MulAssign mul_assign);
+        define_vector!(@scalar $V { $($c),+ } Div div DivAssign div_assign);
+        define_vector!(@scalar $V { $($c),+ } Rem rem RemAssign rem_assign);
 
-    fn sub(self, rhs: Vector2<T>) -> Self::Output {
-        Vector2::<T> {
-            x: self.x - rhs.x,
-            y: self.y - rhs.y,
+        impl<T: Neg<Output = T>> Neg for $V<T> {
+            type Output = $V<T>;
+
+            fn neg(self) -> Self::Output {
+                $V {
+                    $($c: -self.$c),+
+                }
+            }
         }
-    }
-}
 
-impl<T: Mul<Output = T> + Copy> Mul<T> for Vector2<T> {
-    type Output = Vector2<T>;
+        impl<T: Integer> $V<T> {
+            /// Component-wise Euclidean remainder, i.e. every component is wrapped into
+            /// `0..rhs`. Useful for coordinates on a torus.
+            pub fn rem_euclid(self, rhs: Self) -> Self {
+                $V {
+                    $($c: self.$c.rem_euclid(rhs.$c)),+
+                }
+            }
+
+            /// Convert every component with `as` semantics, truncating or sign extending.
+            pub fn wrapping_cast<U: Integer>(self) -> $V<U> {
+                $V {
+                    $($c: U::wrapping_from_i128(self.$c.wrapping_into_i128())),+
+                }
+            }
+        }
 
-    fn mul(self, rhs: T) -> Self::Output {
-        Vector2 {
-            x: self.x * rhs,
-            y: self.y * rhs,
+        impl<T> $V<T> {
+            /// Convert every component, `None` if any of them doesn't fit in `U`.
+            pub fn checked_cast<U: TryFrom<T>>(self) -> Option<$V<U>> {
+                Some($V {
+                    $($c: U::try_from(self.$c).ok()?),+
+                })
+            }
         }
-    }
+
+        impl<T: Mul<Output = T> + Add<Output = T> + Copy + Default> $V<T> {
+            pub fn dot(self, other: Self) -> T {
+                T::default() $(+ self.$c * other.$c)+
+            }
+        }
+
+        impl<T: Abs<Output = T> + Sub<Output = T> + Add<Output = T> + Default> $V<T> {
+            pub fn manhattan_distance(self, other: Self) -> T {
+                T::default() $(+ (self.$c - other.$c).abs())+
+            }
+        }
+
+        impl<T: Abs<Output = T> + Sub<Output = T> + Ord + Default> $V<T> {
+            /// The number of king moves between `self` and `other`.
+            pub fn chebyshev_distance(self, other: Self) -> T {
+                let mut max = T::default();
+                $(max = max.max((self.$c - other.$c).abs());)+
+
+                max
+            }
+        }
+    };
+    (@op $V:ident { $($c:ident),+ } $Op:ident $op:ident $OpAssign:ident $op_assign:ident) => {
+        impl<T: $Op<Output = T>> $Op for $V<T> {
+            type Output = $V<T>;
+
+            fn $op(self, rhs: $V<T>) -> Self::Output {
+                $V {
+                    $($c: self.$c.$op(rhs.$c)),+
+                }
+            }
+        }
+
+        impl<T: $OpAssign> $OpAssign for $V<T> {
+            fn $op_assign(&mut self, rhs: $V<T>) {
+                $(self.$c.$op_assign(rhs.$c);)+
+            }
+        }
+    };
+    (@scalar $V:ident { $($c:ident),+ } $Op:ident $op:ident $OpAssign:ident $op_assign:ident) => {
+        impl<T: $Op<Output = T> + Copy> $Op<T> for $V<T> {
+            type Output = $V<T>;
+
+            fn $op(self, rhs: T) -> Self::Output {
+                $V {
+                    $($c: self.$c.$op(rhs)),+
+                }
+            }
+        }
+
+        impl<T: $OpAssign + Copy> $OpAssign<T> for $V<T> {
+            fn $op_assign(&mut self, rhs: T) {
+                $(self.$c.$op_assign(rhs);)+
+            }
+        }
+    };
 }
 
-impl<T: Neg<Output = T> + Hash + PartialEq> Neg for Vector2<T> {
-    type Output = Vector2<T>;
+define_vector!(Vector2 { x, y });
+define_vector!(Vector3 { x, y, z });
+
+impl<T: Mul<Output = T> + Sub<Output = T> + Copy> Vector2<T> {
+    /// The z component of the cross product of `self` and `other` extended to three dimensions.
+    pub fn cross(self, other: Self) -> T {
+        self.x * other.y - self.y * other.x
+    }
+}
 
-    fn neg(self) -> Self::Output {
-        Vector2::<T> {
-            x: -self.x,
-            y: -self.y,
+impl<T: Mul<Output = T> + Sub<Output = T> + Copy> Vector3<T> {
+    pub fn cross(self, other: Self) -> Self {
+        Self {
+            x: self.y * other.z - self.z * other.y,
+            y: self.z * other.x - self.x * other.z,
+            z: self.x * other.y - self.y * other.x,
         }
     }
 }
@@ -80,21 +193,299 @@ pub trait Abs {
 }
 
 macro_rules! define_abs {
-    ($T:ident) => {
-        impl Abs for $T {
-            type Output = $T;
+    ($($T:ident),+) => {
+        $(
+            impl Abs for $T {
+                type Output = $T;
 
-            fn abs(self) -> Self::Output {
-                self.abs()
+                fn abs(self) -> Self::Output {
+                    self.abs()
+                }
             }
-        }
+        )+
+    };
+}
+
+define_abs!(i8, i16, i32, i64, i128, isize);
+
+/// Primitive integer types.
+pub trait Integer:
+    Copy
+    + Ord
+    + fmt::Debug
+    + Add<Output = Self>
+    + Sub<Output = Self>
+    + Mul<Output = Self>
+    + Div<Output = Self>
+    + Rem<Output = Self>
+{
+    const ZERO: Self;
+    const ONE: Self;
+
+    fn checked_add(self, rhs: Self) -> Option<Self>;
+
+    fn checked_sub(self, rhs: Self) -> Option<Self>;
+
+    fn checked_mul(self, rhs: Self) -> Option<Self>;
+
+    fn rem_euclid(self, rhs: Self) -> Self;
+
+    /// `self as i128`, wrapping for `u128` values above `i128::MAX`.
+    fn wrapping_into_i128(self) -> i128;
+
+    /// `value as Self`.
+    fn wrapping_from_i128(value: i128) -> Self;
+}
+
+/// Primitive unsigned integer types.
+pub trait UnsignedInt: Integer + TryFrom<u32> {}
+
+macro_rules! define_integer {
+    ($($T:ident),+) => {
+        $(
+            impl Integer for $T {
+                const ZERO: Self = 0;
+                const ONE: Self = 1;
+
+                fn checked_add(self, rhs: Self) -> Option<Self> {
+                    self.checked_add(rhs)
+                }
+
+                fn checked_sub(self, rhs: Self) -> Option<Self> {
+                    self.checked_sub(rhs)
+                }
+
+                fn checked_mul(self, rhs: Self) -> Option<Self> {
+                    self.checked_mul(rhs)
+                }
+
+                fn rem_euclid(self, rhs: Self) -> Self {
+                    self.rem_euclid(rhs)
+                }
+
+                fn wrapping_into_i128(self) -> i128 {
+                    self as i128
+                }
+
+                fn wrapping_from_i128(value: i128) -> Self {
+                    value as $T
+                }
+            }
+        )+
     };
 }
 
-define_abs!(isize);
+define_integer!(u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize);
+
+impl UnsignedInt for u8 {}
+impl UnsignedInt for u16 {}
+impl UnsignedInt for u32 {}
+impl UnsignedInt for u64 {}
+impl UnsignedInt for u128 {}
+impl UnsignedInt for usize {}
+
+/// One of the eight compass directions on a grid where `y` increases downwards.
+#[derive(Debug, Copy, Clone, Hash, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Direction {
+    Up,
+    UpRight,
+    Right,
+    DownRight,
+    Down,
+    DownLeft,
+    Left,
+    UpLeft,
+}
+
+impl Direction {
+    /// The four orthogonal directions, clockwise from [`Direction::Up`].
+    pub const CARDINAL: [Self; 4] = [Self::Up, Self::Right, Self::Down, Self::Left];
+    /// All eight directions, clockwise from [`Direction::Up`].
+    pub const ALL: [Self; 8] = [
+        Self::Up,
+        Self::UpRight,
+        Self::Right,
+        Self::DownRight,
+        Self::Down,
+        Self::DownLeft,
+        Self::Left,
+        Self::UpLeft,
+    ];
+
+    /// Turn 90 degrees clockwise.
+    pub fn rotate_right(self) -> Self {
+        self.turn(2)
+    }
+
+    /// Turn 90 degrees counter clockwise.
+    pub fn rotate_left(self) -> Self {
+        self.turn(6)
+    }
+
+    /// Turn 45 degrees clockwise.
+    pub fn rotate_right_45(self) -> Self {
+        self.turn(1)
+    }
+
+    /// Turn 45 degrees counter clockwise.
+    pub fn rotate_left_45(self) -> Self {
+        self.turn(7)
+    }
+
+    pub fn opposite(self) -> Self {
+        self.turn(4)
+    }
+
+    pub fn is_diagonal(self) -> bool {
+        (self as usize) % 2 == 1
+    }
+
+    /// The unit step in this direction.
+    pub const fn offset(self) -> Vector2<isize> {
+        match self {
+            Self::Up => Vector2::new(0, -1),
+            Self::UpRight => Vector2::new(1, -1),
+            Self::Right => Vector2::new(1, 0),
+            Self::DownRight => Vector2::new(1, 1),
+            Self::Down => Vector2::new(0, 1),
+            Self::DownLeft => Vector2::new(-1, 1),
+            Self::Left => Vector2::new(-1, 0),
+            Self::UpLeft => Vector2::new(-1, -1),
+        }
+    }
+
+    fn turn(self, eighths: usize) -> Self {
+        Self::ALL[(self as usize + eighths) % Self::ALL.len()]
+    }
+}
+
+impl From<Direction> for Vector2<isize> {
+    fn from(direction: Direction) -> Self {
+        direction.offset()
+    }
+}
+
+impl TryFrom<Vector2<isize>> for Direction {
+    type Error = Vector2<isize>;
+
+    /// Only unit steps, i.e. vectors with components in `-1..=1` that are not zero, convert.
+    fn try_from(value: Vector2<isize>) -> Result<Self, Self::Error> {
+        Self::ALL
+            .into_iter()
+            .find(|d| d.offset() == value)
+            .ok_or(value)
+    }
+}
+
+impl TryFrom<char> for Direction {
+    type Error = char;
+
+    /// Parse one of the arrows `^`, `>`, `v` or `<`.
+    fn try_from(value: char) -> Result<Self, Self::Error> {
+        match value {
+            '^' => Ok(Self::Up),
+            '>' => Ok(Self::Right),
+            'v' => Ok(Self::Down),
+            '<' => Ok(Self::Left),
+            _ => Err(value),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Direction, Vector2, Vector3};
+
+    #[test]
+    fn test_vector_ops() {
+        let mut v = Vector2::new(3, -4);
+        v += Vector2::new(1, 1);
+        assert_eq!(v, Vector2::new(4, -3));
+        v -= Vector2::new(2, 2);
+        assert_eq!(v, Vector2::new(2, -5));
+        v *= 3;
+        assert_eq!(v, Vector2::new(6, -15));
+        v /= Vector2::new(2, 5);
+        assert_eq!(v, Vector2::new(3, -3));
+
+        assert_eq!(
+            Vector2::new(7, 9) * Vector2::new(2, 3),
+            Vector2::new(14, 27)
+        );
+        assert_eq!(Vector2::new(7, 9) % 4, Vector2::new(3, 1));
+        assert_eq!(
+            Vector2::new(-7, 9) % Vector2::new(4, 5),
+            Vector2::new(-3, 4)
+        );
+        assert_eq!(
+            Vector2::new(-7, 12).rem_euclid(Vector2::new(4, 5)),
+            Vector2::new(1, 2)
+        );
+        assert_eq!(-Vector3::new(1, -2, 3), Vector3::new(-1, 2, -3));
+    }
+
+    #[test]
+    fn test_vector_products_and_distances() {
+        assert_eq!(Vector2::new(2, 3).dot(Vector2::new(4, -5)), -7);
+        assert_eq!(Vector2::new(1, 0).cross(Vector2::new(0, 1)), 1);
+        assert_eq!(Vector3::new(1, 2, 3).dot(Vector3::new(4, 5, 6)), 32);
+        assert_eq!(
+            Vector3::new(1, 0, 0).cross(Vector3::new(0, 1, 0)),
+            Vector3::new(0, 0, 1)
+        );
+
+        let (a, b) = (Vector2::new(1_i64, 5), Vector2::new(-2, 1));
+        assert_eq!(a.manhattan_distance(b), 7);
+        assert_eq!(a.chebyshev_distance(b), 4);
+        assert_eq!(
+            Vector3::new(0_i8, 0, 0).manhattan_distance(Vector3::new(1, -2, 3)),
+            6
+        );
+    }
+
+    #[test]
+    fn test_vector_casts() {
+        assert_eq!(
+            Vector2::new(3_usize, 4).checked_cast::<i32>(),
+            Some(Vector2::new(3, 4))
+        );
+        assert_eq!(Vector2::new(-1_isize, 4).checked_cast::<usize>(), None);
+        assert_eq!(
+            Vector2::new(-1_isize, 300).wrapping_cast::<u8>(),
+            Vector2::new(255, 44)
+        );
+        assert_eq!(
+            Vector3::new(u128::MAX, 1, 2).wrapping_cast::<i64>(),
+            Vector3::new(-1, 1, 2)
+        );
+    }
+
+    #[test]
+    fn test_direction_rotation() {
+        assert_eq!(Direction::Up.rotate_right(), Direction::Right);
+        assert_eq!(Direction::Up.rotate_left(), Direction::Left);
+        assert_eq!(Direction::UpLeft.rotate_right_45(), Direction::Up);
+        assert_eq!(Direction::Up.rotate_left_45(), Direction::UpLeft);
+        assert_eq!(Direction::DownRight.opposite(), Direction::UpLeft);
+
+        for direction in Direction::ALL {
+            assert_eq!(direction.rotate_right().rotate_left(), direction);
+            assert_eq!(direction.opposite().offset(), -direction.offset());
+        }
+    }
+
+    #[test]
+    fn test_direction_conversions() {
+        assert_eq!(Vector2::from(Direction::Left), Vector2::new(-1, 0));
+        assert_eq!(
+            Direction::try_from(Vector2::new(1, 1)),
+            Ok(Direction::DownRight)
+        );
+        assert!(Direction::try_from(Vector2::new(2, 0)).is_err());
+        assert!(Direction::try_from(Vector2::new(0, 0)).is_err());
 
-impl<T: Abs<Output = T> + Sub<Output = T> + Add<Output = T>> Vector2<T> {
-    pub fn manhattan_distance(self, other: Self) -> T {
-        (self.x - other.x).abs() + (self.y - other.y).abs()
+        let arrows: Result<Vec<_>, _> = "^>v<".chars().map(Direction::try_from).collect();
+        assert_eq!(arrows, Ok(Direction::CARDINAL.to_vec()));
+        assert_eq!(Direction::try_from('x'), Err('x'));
     }
 }
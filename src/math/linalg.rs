@@ -0,0 +1,361 @@
+use std::cmp::Ordering;
+use std::fmt;
+use std::ops::{Add, Div, Mul, Neg, Sub};
+
+use super::num::gcd;
+use super::{Vector2, Vector3};
+
+/// An exact fraction, always kept in lowest terms with a positive denominator.
+///
+/// Arithmetic happens in `i128`, which is plenty for systems with coefficients of a moderate size
+/// but will overflow (and panic in debug builds) for huge ones.
+#[derive(Copy, Clone, PartialEq, Eq, Hash)]
+pub struct Rational {
+    numerator: i128,
+    denominator: i128,
+}
+
+impl Rational {
+    pub const ZERO: Self = Self::integer(0);
+    pub const ONE: Self = Self::integer(1);
+
+    /// **Note:** Panics if `denominator` is zero
+    pub fn new(numerator: i128, denominator: i128) -> Self {
+        assert!(denominator != 0, "Denominator cannot be zero");
+
+        let divisor = gcd(numerator, denominator) * denominator.signum();
+
+        Self {
+            numerator: numerator / divisor,
+            denominator: denominator / divisor,
+        }
+    }
+
+    pub const fn integer(value: i128) -> Self {
+        Self {
+            numerator: value,
+            denominator: 1,
+        }
+    }
+
+    pub fn numerator(&self) -> i128 {
+        self.numerator
+    }
+
+    pub fn denominator(&self) -> i128 {
+        self.denominator
+    }
+
+    pub fn is_integer(&self) -> bool {
+        self.denominator == 1
+    }
+
+    pub fn to_integer(self) -> Option<i128> {
+        self.is_integer().then_some(self.numerator)
+    }
+
+    pub fn is_zero(&self) -> bool {
+        self.numerator == 0
+    }
+}
+
+impl From<i128> for Rational {
+    fn from(value: i128) -> Self {
+        Self::integer(value)
+    }
+}
+
+impl From<i64> for Rational {
+    fn from(value: i64) -> Self {
+        Self::integer(value.into())
+    }
+}
+
+impl Add for Rational {
+    type Output = Rational;
+
+    fn add(self, rhs: Self) -> Self::Output {
+        Rational::new(
+            self.numerator * rhs.denominator + rhs.numerator * self.denominator,
+            self.denominator * rhs.denominator,
+        )
+    }
+}
+
+impl Sub for Rational {
+    type Output = Rational;
+
+    fn sub(self, rhs: Self) -> Self::Output {
+        self + -rhs
+    }
+}
+
+impl Mul for Rational {
+    type Output = Rational;
+
+    fn mul(self, rhs: Self) -> Self::Output {
+        Rational::new(
+            self.numerator * rhs.numerator,
+            self.denominator * rhs.denominator,
+        )
+    }
+}
+
+impl Div for Rational {
+    type Output = Rational;
+
+    /// **Note:** Panics if `rhs` is zero
+    fn div(self, rhs: Self) -> Self::Output {
+        Rational::new(
+            self.numerator * rhs.denominator,
+            self.denominator * rhs.numerator,
+        )
+    }
+}
+
+impl Neg for Rational {
+    type Output = Rational;
+
+    fn neg(self) -> Self::Output {
+        Rational {
+            numerator: -self.numerator,
+            denominator: self.denominator,
+        }
+    }
+}
+
+impl Ord for Rational {
+    fn cmp(&self, other: &Self) -> Ordering {
+        (self.numerator * other.denominator).cmp(&(other.numerator * self.denominator))
+    }
+}
+
+impl PartialOrd for Rational {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl fmt::Display for Rational {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.is_integer() {
+            write!(f, "{}", self.numerator)
+        } else {
+            write!(f, "{}/{}", self.numerator, self.denominator)
+        }
+    }
+}
+
+impl fmt::Debug for Rational {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{self}")
+    }
+}
+
+/// The result of solving a linear system exactly.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Outcome<I, R> {
+    /// Exactly one solution and all of its components are integers.
+    Integral(I),
+    /// Exactly one solution but some of its components aren't integers.
+    NonIntegral(R),
+    /// No solution at all, e.g. parallel lines.
+    Inconsistent,
+    /// Infinitely many solutions, e.g. the same line twice.
+    Infinite,
+}
+
+impl<I, R> Outcome<I, R> {
+    /// The unique integral solution, if any.
+    pub fn integral(self) -> Option<I> {
+        match self {
+            Self::Integral(solution) => Some(solution),
+            _ => None,
+        }
+    }
+}
+
+/// Solve `x * a + y * b == target` for `(x, y)` using Cramer's rule, i.e. the system whose
+/// matrix has the columns `a` and `b`.
+pub fn solve2(
+    a: Vector2<i64>,
+    b: Vector2<i64>,
+    target: Vector2<i64>,
+) -> Outcome<Vector2<i128>, Vector2<Rational>> {
+    let [a, b, target] = [a, b, target].map(|v| v.map(i128::from));
+    let det = a.cross(b);
+
+    if det == 0 {
+        let zero = Vector2::new(0, 0);
+        // Every column lies on the same line through the origin, solvable iff target is on it
+        let consistent = if a == zero && b == zero {
+            target == zero
+        } else {
+            a.cross(target) == 0 && b.cross(target) == 0
+        };
+
+        return if consistent {
+            Outcome::Infinite
+        } else {
+            Outcome::Inconsistent
+        };
+    }
+
+    let x = Rational::new(target.cross(b), det);
+    let y = Rational::new(a.cross(target), det);
+
+    match (x.to_integer(), y.to_integer()) {
+        (Some(x), Some(y)) => Outcome::Integral(Vector2::new(x, y)),
+        _ => Outcome::NonIntegral(Vector2::new(x, y)),
+    }
+}
+
+/// Solve `x * columns[0] + y * columns[1] + z * columns[2] == target` for `(x, y, z)`.
+pub fn solve3(
+    columns: [Vector3<i64>; 3],
+    target: Vector3<i64>,
+) -> Outcome<Vector3<i128>, Vector3<Rational>> {
+    let matrix = [
+        [columns[0].x, columns[1].x, columns[2].x],
+        [columns[0].y, columns[1].y, columns[2].y],
+        [columns[0].z, columns[1].z, columns[2].z],
+    ];
+
+    match solve(matrix, [target.x, target.y, target.z]) {
+        Outcome::Integral([x, y, z]) => Outcome::Integral(Vector3::new(x, y, z)),
+        Outcome::NonIntegral([x, y, z]) => Outcome::NonIntegral(Vector3::new(x, y, z)),
+        Outcome::Inconsistent => Outcome::Inconsistent,
+        Outcome::Infinite => Outcome::Infinite,
+    }
+}
+
+/// Solve `matrix * x == rhs` using Gaussian elimination over rationals, `matrix` is row major.
+pub fn solve<const N: usize>(
+    matrix: [[i64; N]; N],
+    rhs: [i64; N],
+) -> Outcome<[i128; N], [Rational; N]> {
+    // Augmented matrix, the last column is the right hand side
+    let mut rows: Vec<Vec<Rational>> = matrix
+        .iter()
+        .zip(rhs)
+        .map(|(row, r)| row.iter().chain([&r]).map(|v| Rational::from(*v)).collect())
+        .collect();
+
+    // Reduce to reduced row echelon form, `rank` rows have a pivot so far
+    let mut rank = 0;
+    for column in 0..N {
+        let Some(pivot) = (rank..N).find(|r| !rows[*r][column].is_zero()) else {
+            continue;
+        };
+        rows.swap(rank, pivot);
+
+        let pivot_row = rows[rank].clone();
+        for (r, row) in rows.iter_mut().enumerate() {
+            if r == rank || row[column].is_zero() {
+                continue;
+            }
+
+            let factor = row[column] / pivot_row[column];
+            for (value, pivot_value) in row[column..].iter_mut().zip(&pivot_row[column..]) {
+                *value = *value - factor * *pivot_value;
+            }
+        }
+        rank += 1;
+    }
+
+    // Rows without a pivot are all zero on the left, they must be zero on the right too
+    if rows[rank..].iter().any(|row| !row[N].is_zero()) {
+        return Outcome::Inconsistent;
+    }
+    if rank < N {
+        return Outcome::Infinite;
+    }
+
+    let solution: [Rational; N] = std::array::from_fn(|i| rows[i][N] / rows[i][i]);
+    if solution.iter().all(Rational::is_integer) {
+        Outcome::Integral(solution.map(|v| v.numerator()))
+    } else {
+        Outcome::NonIntegral(solution)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{solve, solve2, solve3, Outcome, Rational};
+    use crate::math::{Vector2, Vector3};
+
+    #[test]
+    fn test_rational() {
+        let half = Rational::new(2, 4);
+        assert_eq!((half.numerator(), half.denominator()), (1, 2));
+        assert_eq!(Rational::new(3, -6), -half);
+        assert_eq!(half + Rational::new(1, 3), Rational::new(5, 6));
+        assert_eq!(half - Rational::ONE, Rational::new(-1, 2));
+        assert_eq!(half * Rational::integer(4), Rational::integer(2));
+        assert_eq!(half / Rational::new(1, 4), Rational::integer(2));
+        assert!(Rational::new(-1, 2) < Rational::new(1, 3));
+        assert_eq!(Rational::new(6, 3).to_integer(), Some(2));
+        assert_eq!(half.to_integer(), None);
+        assert_eq!(Rational::new(0, -5), Rational::ZERO);
+        assert_eq!(Rational::new(-7, 3).to_string(), "-7/3");
+    }
+
+    #[test]
+    fn test_solve2() {
+        // Claw machine: A moves (94, 34), B moves (22, 67), prize at (8400, 5400)
+        assert_eq!(
+            solve2(
+                Vector2::new(94, 34),
+                Vector2::new(22, 67),
+                Vector2::new(8400, 5400)
+            ),
+            Outcome::Integral(Vector2::new(80, 40))
+        );
+        assert_eq!(
+            solve2(Vector2::new(2, 0), Vector2::new(0, 2), Vector2::new(1, 4)),
+            Outcome::NonIntegral(Vector2::new(Rational::new(1, 2), Rational::integer(2)))
+        );
+        assert_eq!(
+            solve2(Vector2::new(1, 2), Vector2::new(2, 4), Vector2::new(3, 6)),
+            Outcome::Infinite
+        );
+        assert_eq!(
+            solve2(Vector2::new(1, 2), Vector2::new(2, 4), Vector2::new(3, 7)),
+            Outcome::Inconsistent
+        );
+        assert_eq!(
+            solve2(Vector2::new(0, 0), Vector2::new(0, 0), Vector2::new(0, 0)),
+            Outcome::Infinite
+        );
+        assert_eq!(
+            solve2(Vector2::new(0, 0), Vector2::new(0, 0), Vector2::new(1, 0)),
+            Outcome::Inconsistent
+        );
+    }
+
+    #[test]
+    fn test_solve3() {
+        // x + y + z = 6, 2y + 5z = -4, 2x + 5y - z = 27
+        let columns = [
+            Vector3::new(1, 0, 2),
+            Vector3::new(1, 2, 5),
+            Vector3::new(1, 5, -1),
+        ];
+        assert_eq!(
+            solve3(columns, Vector3::new(6, -4, 27)),
+            Outcome::Integral(Vector3::new(5, 3, -2))
+        );
+        assert!(matches!(
+            solve3(columns, Vector3::new(1, 0, 0)),
+            Outcome::NonIntegral(_)
+        ));
+
+        // The third row is the sum of the first two
+        let matrix = [[1, 2, 3], [4, 5, 6], [5, 7, 9]];
+        assert_eq!(solve(matrix, [1, 2, 3]), Outcome::Infinite);
+        assert_eq!(solve(matrix, [1, 2, 4]), Outcome::Inconsistent);
+
+        // Needs a row swap
+        assert_eq!(solve([[0, 1], [1, 0]], [3, 4]), Outcome::Integral([4, 3]));
+    }
+}
@@ -0,0 +1,207 @@
+use super::Integer;
+
+/// Greatest common divisor, always non-negative. `gcd(0, 0) == 0`.
+///
+/// **Note:** Panics if `a` or `b` is the minimum of a signed type, its magnitude doesn't fit
+pub fn gcd<T: Integer>(a: T, b: T) -> T {
+    let (mut a, mut b) = (abs(a), abs(b));
+    while b != T::ZERO {
+        (a, b) = (b, a % b);
+    }
+
+    a
+}
+
+/// Least common multiple, always non-negative. `lcm(0, x) == 0`.
+///
+/// **Note:** Panics if `a` or `b` is the minimum of a signed type, its magnitude doesn't fit
+pub fn lcm<T: Integer>(a: T, b: T) -> T {
+    if a == T::ZERO || b == T::ZERO {
+        return T::ZERO;
+    }
+
+    abs(a / gcd(a, b) * b)
+}
+
+/// The extended Euclidean algorithm, returns `(g, x, y)` such that `a * x + b * y == g` where `g`
+/// is `gcd(a, b)`.
+pub fn extended_gcd(a: i64, b: i64) -> (i64, i64, i64) {
+    let (g, x, y) = extended_gcd_wide(a.into(), b.into());
+
+    (g as i64, x as i64, y as i64)
+}
+
+/// The inverse of `a` modulo `modulus` in `0..modulus`, `None` if `a` and `modulus` aren't
+/// coprime.
+///
+/// **Note:** Panics if `modulus` isn't positive
+pub fn mod_inverse(a: i64, modulus: i64) -> Option<i64> {
+    assert!(modulus > 0, "Modulus must be positive, got {modulus}");
+
+    let (g, x, _) = extended_gcd_wide(a.into(), modulus.into());
+
+    (g == 1).then(|| x.rem_euclid(modulus.into()) as i64)
+}
+
+/// `base.pow(exp) % modulus` without overflowing.
+///
+/// **Note:** Panics if `modulus` is zero
+pub fn mod_pow(base: u64, mut exp: u64, modulus: u64) -> u64 {
+    assert!(modulus > 0, "Modulus must be positive");
+
+    let modulus = u128::from(modulus);
+    let mut base = u128::from(base) % modulus;
+    let mut result = 1 % modulus;
+    while exp > 0 {
+        if exp & 1 == 1 {
+            result = result * base % modulus;
+        }
+        base = base * base % modulus;
+        exp >>= 1;
+    }
+
+    result as u64
+}
+
+/// Solve a system of congruences `x ≡ residue (mod modulus)` given as `(residue, modulus)` pairs.
+///
+/// The moduli don't need to be coprime. Returns `(x, lcm)` where `x` is the smallest
+/// non-negative solution and every solution is `x + k * lcm` with `lcm` the least common multiple
+/// of the moduli. `None` if the system is inconsistent or `lcm` doesn't fit in an `i64`.
+///
+/// **Note:** Panics if any modulus isn't positive
+pub fn crt(congruences: &[(i64, i64)]) -> Option<(i64, i64)> {
+    let mut x: i128 = 0;
+    let mut m: i128 = 1;
+
+    for &(residue, modulus) in congruences {
+        assert!(modulus > 0, "Modulus must be positive, got {modulus}");
+
+        let (residue, modulus) = (i128::from(residue), i128::from(modulus));
+        // Find k such that x + k * m ≡ residue (mod modulus)
+        let (g, p, _) = extended_gcd_wide(m, modulus);
+        let diff = residue - x;
+        if diff % g != 0 {
+            return None;
+        }
+
+        let step = modulus / g;
+        let k = (diff / g % step * p).rem_euclid(step);
+        x += k * m;
+        m *= step;
+        if m > i64::MAX.into() {
+            return None;
+        }
+        x = x.rem_euclid(m);
+    }
+
+    Some((x as i64, m as i64))
+}
+
+fn extended_gcd_wide(a: i128, b: i128) -> (i128, i128, i128) {
+    let (mut old_r, mut r) = (a, b);
+    let (mut old_s, mut s) = (1, 0);
+    let (mut old_t, mut t) = (0, 1);
+
+    while r != 0 {
+        let q = old_r / r;
+        (old_r, r) = (r, old_r - q * r);
+        (old_s, s) = (s, old_s - q * s);
+        (old_t, t) = (t, old_t - q * t);
+    }
+
+    if old_r < 0 {
+        (-old_r, -old_s, -old_t)
+    } else {
+        (old_r, old_s, old_t)
+    }
+}
+
+/// **Note:** Panics if `value` is the minimum of a signed type, in release builds too
+fn abs<T: Integer>(value: T) -> T {
+    if value < T::ZERO {
+        T::ZERO
+            .checked_sub(value)
+            .unwrap_or_else(|| panic!("Magnitude of {value:?} doesn't fit"))
+    } else {
+        value
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{crt, extended_gcd, gcd, lcm, mod_inverse, mod_pow};
+
+    #[test]
+    #[should_panic(expected = "Magnitude of -9223372036854775808 doesn't fit")]
+    fn test_gcd_min() {
+        gcd(i64::MIN, 0);
+    }
+
+    #[test]
+    fn test_gcd_lcm() {
+        assert_eq!(gcd(12_u32, 18), 6);
+        assert_eq!(gcd(-12_i64, 18), 6);
+        assert_eq!(gcd(12_i8, -18), 6);
+        assert_eq!(gcd(0_usize, 7), 7);
+        assert_eq!(gcd(0_i32, 0), 0);
+        assert_eq!(gcd(17_u128, 5), 1);
+        assert_eq!(gcd(i64::MIN + 1, 0), i64::MAX);
+
+        assert_eq!(lcm(4_u64, 6), 12);
+        assert_eq!(lcm(-4_i64, 6), 12);
+        assert_eq!(lcm(0_i32, 6), 0);
+        assert_eq!([2_u64, 3, 4, 5].into_iter().fold(1, lcm), 60);
+    }
+
+    #[test]
+    fn test_extended_gcd() {
+        for (a, b) in [(240, 46), (46, 240), (-240, 46), (7, 0), (0, -7), (17, 5)] {
+            let (g, x, y) = extended_gcd(a, b);
+
+            assert_eq!(g, gcd(a, b), "gcd of {a} and {b}");
+            assert_eq!(a * x + b * y, g, "Bézout identity for {a} and {b}");
+        }
+    }
+
+    #[test]
+    fn test_mod_inverse() {
+        assert_eq!(mod_inverse(3, 11), Some(4));
+        assert_eq!(mod_inverse(-3, 11), Some(7));
+        assert_eq!(mod_inverse(10, 17), Some(12));
+        assert_eq!(mod_inverse(6, 9), None);
+        assert_eq!(mod_inverse(5, 1), Some(0));
+    }
+
+    #[test]
+    fn test_mod_pow() {
+        assert_eq!(mod_pow(2, 10, 1000), 24);
+        assert_eq!(mod_pow(3, 0, 7), 1);
+        assert_eq!(mod_pow(3, 0, 1), 0);
+        assert_eq!(mod_pow(u64::MAX, 2, u64::MAX - 1), 1);
+        // Fermat's little theorem
+        assert_eq!(mod_pow(123_456_789, 1_000_000_006, 1_000_000_007), 1);
+    }
+
+    #[test]
+    fn test_crt() {
+        assert_eq!(crt(&[(2, 3), (3, 5), (2, 7)]), Some((23, 105)));
+        assert_eq!(crt(&[(-1, 3), (-2, 5)]), Some((8, 15)));
+        // Non coprime moduli
+        assert_eq!(crt(&[(2, 4), (4, 6)]), Some((10, 12)));
+        assert_eq!(crt(&[(1, 4), (2, 6)]), None);
+        assert_eq!(crt(&[(3, 6), (3, 6)]), Some((3, 6)));
+        assert_eq!(crt(&[]), Some((0, 1)));
+
+        // Large coprime moduli
+        let (p, q) = (1_000_000_007, 998_244_353);
+        let x = 123_456_789_012_345;
+        assert_eq!(crt(&[(x % p, p), (x % q, q)]), Some((x, p * q)));
+
+        // Combined modulus overflows
+        assert_eq!(
+            crt(&[(0, 1_000_000_007), (0, 998_244_353), (0, 1_000_000_009)]),
+            None
+        );
+    }
+}
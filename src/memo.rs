@@ -0,0 +1,314 @@
+use std::cell::RefCell;
+use std::collections::{HashMap, VecDeque};
+use std::fmt;
+use std::hash::Hash;
+use std::ops::{Add, AddAssign};
+use std::rc::Rc;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+
+thread_local! {
+    /// Where memos dropped on this thread add their statistics, set by [`collect`].
+    static SINK: RefCell<Option<Arc<Sink>>> = const { RefCell::new(None) };
+}
+
+/// Cache hits and misses of a [`Memo`].
+#[derive(Debug, Copy, Clone, Default, PartialEq, Eq)]
+pub struct Stats {
+    pub hits: u64,
+    pub misses: u64,
+}
+
+impl Stats {
+    pub fn lookups(&self) -> u64 {
+        self.hits + self.misses
+    }
+
+    /// The fraction of lookups that were hits, zero without lookups.
+    pub fn hit_rate(&self) -> f64 {
+        if self.lookups() == 0 {
+            0.0
+        } else {
+            self.hits as f64 / self.lookups() as f64
+        }
+    }
+}
+
+impl Add for Stats {
+    type Output = Stats;
+
+    fn add(self, rhs: Self) -> Self::Output {
+        Stats {
+            hits: self.hits + rhs.hits,
+            misses: self.misses + rhs.misses,
+        }
+    }
+}
+
+impl AddAssign for Stats {
+    fn add_assign(&mut self, rhs: Self) {
+        *self = *self + rhs;
+    }
+}
+
+/// Run `f`, returning the statistics of every [`Memo`] dropped during it, used by
+/// [`crate::bench`] to report caching behaviour. Worker threads count when they run through a
+/// [`Collector`], as [`crate::parallel::Strategy`] does.
+///
+/// Calls nest, memos count towards the innermost one only.
+pub fn collect<R>(f: impl FnOnce() -> R) -> (R, Stats) {
+    let sink = Arc::new(Sink::default());
+    let result = Collector(Some(Arc::clone(&sink))).run(f);
+
+    (result, sink.stats())
+}
+
+/// The current thread's [`collect`] call, for handing to worker threads.
+pub fn collector() -> Collector {
+    Collector(SINK.with_borrow(Clone::clone))
+}
+
+/// Adds the statistics of memos dropped within [`Collector::run`] to the [`collect`] call it was
+/// created in, if any.
+#[derive(Debug, Clone)]
+pub struct Collector(Option<Arc<Sink>>);
+
+impl Collector {
+    pub fn run<R>(&self, f: impl FnOnce() -> R) -> R {
+        /// Puts back the previous sink, also when `f` panics.
+        struct Restore(Option<Arc<Sink>>);
+
+        impl Drop for Restore {
+            fn drop(&mut self) {
+                SINK.set(self.0.take());
+            }
+        }
+
+        let _restore = Restore(SINK.replace(self.0.clone()));
+
+        f()
+    }
+}
+
+#[derive(Debug, Default)]
+struct Sink {
+    hits: AtomicU64,
+    misses: AtomicU64,
+}
+
+impl Sink {
+    fn stats(&self) -> Stats {
+        Stats {
+            hits: self.hits.load(Ordering::Relaxed),
+            misses: self.misses.load(Ordering::Relaxed),
+        }
+    }
+}
+
+type Function<'a, K, V> = Rc<dyn Fn(&mut Memo<'a, K, V>, K) -> V + 'a>;
+
+/// A memoized recursive function.
+///
+/// The function receives the memo itself to recurse through it:
+///
+/// ```
+/// use advent_of_rust_2024::memo::Memo;
+///
+/// let mut fib = Memo::new(|fib: &mut Memo<u64, u64>, n| {
+///     if n < 2 {
+///         n
+///     } else {
+///         fib.get(n - 1) + fib.get(n - 2)
+///     }
+/// });
+///
+/// assert_eq!(fib.get(90), 2880067194370816120);
+/// ```
+pub struct Memo<'a, K, V> {
+    function: Function<'a, K, V>,
+    cache: HashMap<K, V>,
+    /// Keys in insertion order, only tracked when bounded.
+    order: VecDeque<K>,
+    capacity: Option<usize>,
+    stats: Stats,
+}
+
+impl<'a, K: Clone + Eq + Hash, V: Clone> Memo<'a, K, V> {
+    pub fn new(function: impl Fn(&mut Memo<'a, K, V>, K) -> V + 'a) -> Self {
+        Self {
+            function: Rc::new(function),
+            cache: HashMap::new(),
+            order: VecDeque::new(),
+            capacity: None,
+            stats: Stats::default(),
+        }
+    }
+
+    /// A memo that keeps at most `capacity` results, evicting the oldest first.
+    ///
+    /// **Note:** Panics if `capacity` is zero
+    pub fn bounded(capacity: usize, function: impl Fn(&mut Memo<'a, K, V>, K) -> V + 'a) -> Self {
+        assert!(capacity > 0, "Capacity must be positive");
+
+        let mut memo = Self::new(function);
+        memo.capacity = Some(capacity);
+
+        memo
+    }
+
+    /// The result of the function for `key`, computing it only if it isn't cached.
+    pub fn get(&mut self, key: K) -> V {
+        if let Some(value) = self.cache.get(&key) {
+            self.stats.hits += 1;
+            return value.clone();
+        }
+
+        self.stats.misses += 1;
+        let function = Rc::clone(&self.function);
+        let value = function(self, key.clone());
+        self.insert(key, value.clone());
+
+        value
+    }
+
+    pub fn stats(&self) -> Stats {
+        self.stats
+    }
+
+    /// The number of cached results.
+    pub fn len(&self) -> usize {
+        self.cache.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.cache.is_empty()
+    }
+
+    /// Forget all cached results, the statistics are kept.
+    pub fn clear(&mut self) {
+        self.cache.clear();
+        self.order.clear();
+    }
+
+    fn insert(&mut self, key: K, value: V) {
+        let Some(capacity) = self.capacity else {
+            self.cache.insert(key, value);
+            return;
+        };
+
+        // A recursive call might have cached the same key already
+        if self.cache.insert(key.clone(), value).is_none() {
+            self.order.push_back(key);
+        }
+        while self.cache.len() > capacity {
+            let oldest = self.order.pop_front().expect("Order tracks every key");
+            self.cache.remove(&oldest);
+        }
+    }
+}
+
+impl<K, V> Drop for Memo<'_, K, V> {
+    fn drop(&mut self) {
+        SINK.with_borrow(|sink| {
+            if let Some(sink) = sink {
+                sink.hits.fetch_add(self.stats.hits, Ordering::Relaxed);
+                sink.misses.fetch_add(self.stats.misses, Ordering::Relaxed);
+            }
+        });
+    }
+}
+
+impl<K, V> fmt::Debug for Memo<'_, K, V> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Memo")
+            .field("len", &self.cache.len())
+            .field("capacity", &self.capacity)
+            .field("stats", &self.stats)
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::thread;
+
+    use super::{collect, collector, Memo, Stats};
+    use crate::parallel::Strategy;
+
+    fn fib<'a>() -> Memo<'a, u64, u64> {
+        Memo::new(|fib: &mut Memo<u64, u64>, n| {
+            if n < 2 {
+                n
+            } else {
+                fib.get(n - 1) + fib.get(n - 2)
+            }
+        })
+    }
+
+    #[test]
+    fn test_memo() {
+        let mut fib = fib();
+
+        assert_eq!(fib.get(10), 55);
+        assert_eq!(
+            fib.stats(),
+            Stats {
+                hits: 8,
+                misses: 11
+            }
+        );
+        assert_eq!(fib.len(), 11);
+
+        assert_eq!(fib.get(10), 55);
+        assert_eq!(fib.stats().hits, 9);
+        assert!((fib.stats().hit_rate() - 0.45).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_captures() {
+        let stones = [125_u64, 17];
+        let mut memo = Memo::new(|memo: &mut Memo<usize, u64>, i| {
+            stones[i] + if i == 0 { 0 } else { memo.get(i - 1) }
+        });
+
+        assert_eq!(memo.get(1), 142);
+    }
+
+    #[test]
+    fn test_bounded() {
+        let mut squares = Memo::bounded(2, |_: &mut Memo<u64, u64>, n| n * n);
+
+        squares.get(1);
+        squares.get(2);
+        squares.get(3);
+        assert_eq!(squares.len(), 2);
+
+        // 1 was evicted, 3 is still cached
+        squares.get(1);
+        squares.get(3);
+        assert_eq!(squares.stats(), Stats { hits: 1, misses: 4 });
+    }
+
+    #[test]
+    fn test_collect() {
+        let ((), stats) = collect(|| {
+            fib().get(10);
+            // Memos dropped on worker threads count too
+            Strategy::Threads.map(&[3], |n| fib().get(*n));
+            // Memos in a nested call don't count here
+            assert_eq!(collect(|| fib().get(2)).1, Stats { hits: 0, misses: 3 });
+            // Nor do threads that don't run through a collector
+            thread::spawn(|| fib().get(5)).join().unwrap();
+            assert!(collector().0.is_some());
+        });
+
+        assert_eq!(
+            stats,
+            Stats {
+                hits: 8 + 1,
+                misses: 11 + 4
+            }
+        );
+        assert!(collector().0.is_none());
+    }
+}
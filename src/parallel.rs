@@ -0,0 +1,84 @@
+use std::num::NonZeroUsize;
+use std::thread;
+
+use crate::memo;
+
+/// How to run independent checks, e.g. every candidate obstruction in day06.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Strategy {
+    Serial,
+    /// Split the work across one scoped thread per available core.
+    Threads,
+}
+
+impl Strategy {
+    /// `f` applied to every item, in the same order as `items`.
+    pub fn map<T: Sync, U: Send>(self, items: &[T], f: impl Fn(&T) -> U + Sync) -> Vec<U> {
+        match self {
+            Self::Serial => items.iter().map(f).collect(),
+            Self::Threads => map_threaded(items, f),
+        }
+    }
+
+    /// The number of items matching `predicate`.
+    pub fn count<T: Sync>(self, items: &[T], predicate: impl Fn(&T) -> bool + Sync) -> usize {
+        self.map(items, predicate)
+            .into_iter()
+            .filter(|m| *m)
+            .count()
+    }
+}
+
+impl Default for Strategy {
+    /// [`Strategy::Threads`] with the `parallel` feature enabled, [`Strategy::Serial`] otherwise.
+    fn default() -> Self {
+        if cfg!(feature = "parallel") {
+            Self::Threads
+        } else {
+            Self::Serial
+        }
+    }
+}
+
+fn map_threaded<T: Sync, U: Send>(items: &[T], f: impl Fn(&T) -> U + Sync) -> Vec<U> {
+    let threads = thread::available_parallelism().map_or(1, NonZeroUsize::get);
+    let chunk_size = items.len().div_ceil(threads).max(1);
+    let f = &f;
+    // Memos dropped by workers count towards the caller's `memo::collect`
+    let collector = &memo::collector();
+
+    thread::scope(|scope| {
+        let handles: Vec<_> = items
+            .chunks(chunk_size)
+            .map(|chunk| {
+                scope.spawn(move || collector.run(|| chunk.iter().map(f).collect::<Vec<_>>()))
+            })
+            .collect();
+
+        handles
+            .into_iter()
+            .flat_map(|h| h.join().expect("Worker thread panicked"))
+            .collect()
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Strategy;
+
+    #[test]
+    fn test_strategies() {
+        let items: Vec<u64> = (0..1000).collect();
+        let square = |x: &u64| x * x;
+
+        let serial = Strategy::Serial.map(&items, square);
+        assert_eq!(Strategy::Threads.map(&items, square), serial);
+        assert_eq!(serial[999], 998001);
+
+        let even = |x: &u64| x.is_multiple_of(2);
+        assert_eq!(Strategy::Threads.count(&items, even), 500);
+        assert_eq!(Strategy::Serial.count(&items, even), 500);
+
+        assert!(Strategy::Threads.map(&[] as &[u64], square).is_empty());
+    }
+}
@@ -0,0 +1,557 @@
+use std::any::type_name;
+use std::fmt;
+use std::str::FromStr;
+
+use crate::grid::Grid;
+use crate::ParseError;
+
+/// The parsed value and the remaining input.
+pub type Parsed<'a, T> = Result<(T, &'a str), Failure<'a>>;
+
+/// A parser of `T` from the start of its input, combined with the functions in this module and
+/// run with [`complete`].
+pub trait Parser<'a, T>: Fn(&'a str) -> Parsed<'a, T> {}
+
+impl<'a, T, F: Fn(&'a str) -> Parsed<'a, T>> Parser<'a, T> for F {}
+
+/// Why a parser failed, pointing into the input it was given.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Failure<'a> {
+    fragment: &'a str,
+    expected: String,
+    cut: bool,
+}
+
+impl<'a> Failure<'a> {
+    /// The input at `rest` isn't what the parser looks for, alternatives may still match.
+    pub fn backtrack(rest: &'a str, expected: impl Into<String>) -> Self {
+        Self {
+            fragment: token(rest),
+            expected: expected.into(),
+            cut: false,
+        }
+    }
+
+    /// `fragment` was recognised but is invalid, e.g. an integer that overflows. Stops
+    /// alternatives from being tried and isn't relabeled by [`context`].
+    pub fn cut(fragment: &'a str, expected: impl Into<String>) -> Self {
+        Self {
+            fragment,
+            expected: expected.into(),
+            cut: true,
+        }
+    }
+
+    pub fn is_cut(&self) -> bool {
+        self.cut
+    }
+
+    /// The error with its position within `input`.
+    ///
+    /// **Note:** Panics if the failure didn't come from parsing `input`
+    pub fn into_error(self, input: &str) -> ParseError {
+        ParseError::at(input, self.fragment, self.expected)
+    }
+}
+
+impl fmt::Display for Failure<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "expected {} but found `{}`",
+            self.expected, self.fragment
+        )
+    }
+}
+
+/// Run `parser` on all of `input`, only trailing whitespace may be left over.
+pub fn complete<'a, T>(input: &'a str, parser: impl Parser<'a, T>) -> Result<T, ParseError> {
+    let (value, rest) = parser(input).map_err(|f| f.into_error(input))?;
+    let rest = rest.trim_start();
+    if !rest.is_empty() {
+        return Err(ParseError::at(input, token(rest), "end of input"));
+    }
+
+    Ok(value)
+}
+
+/// Exactly `literal`.
+pub fn tag<'a>(literal: &'static str) -> impl Parser<'a, &'a str> {
+    move |input: &'a str| match input.strip_prefix(literal) {
+        Some(rest) => Ok((&input[..literal.len()], rest)),
+        None => Err(Failure::backtrack(input, format!("`{literal}`"))),
+    }
+}
+
+/// An integer with an optional sign, e.g. `-12` or `+7`.
+///
+/// The whole alphanumeric token is parsed, so `1x9` is an invalid `T` rather than `1` followed
+/// by `x9`.
+pub fn integer<'a, T: FromStr>() -> impl Parser<'a, T> {
+    number(true)
+}
+
+/// Like [`integer`] but without a sign, e.g. `12`.
+pub fn unsigned<'a, T: FromStr>() -> impl Parser<'a, T> {
+    number(false)
+}
+
+fn number<'a, T: FromStr>(signed: bool) -> impl Parser<'a, T> {
+    move |input: &'a str| {
+        let sign = usize::from(signed && input.starts_with(['-', '+']));
+        if sign == 0 && !input.starts_with(|c: char| c.is_ascii_digit()) {
+            return Err(Failure::backtrack(input, type_name::<T>()));
+        }
+
+        let end = input[sign..]
+            .find(|c: char| !c.is_alphanumeric())
+            .map_or(input.len(), |i| i + sign);
+        let (number, rest) = input.split_at(end);
+
+        number
+            .parse()
+            .map(|value| (value, rest))
+            .map_err(|_| Failure::cut(number, type_name::<T>()))
+    }
+}
+
+/// Any number of spaces or tabs.
+pub fn spaces0<'a>() -> impl Parser<'a, &'a str> {
+    move |input: &'a str| {
+        let rest = input.trim_start_matches([' ', '\t']);
+
+        Ok((&input[..input.len() - rest.len()], rest))
+    }
+}
+
+/// At least one space or tab.
+pub fn spaces1<'a>() -> impl Parser<'a, &'a str> {
+    move |input: &'a str| match spaces0()(input)? {
+        ("", _) => Err(Failure::backtrack(input, "whitespace")),
+        result => Ok(result),
+    }
+}
+
+/// Any amount of whitespace, including newlines.
+pub fn whitespace0<'a>() -> impl Parser<'a, &'a str> {
+    move |input: &'a str| {
+        let rest = input.trim_start();
+
+        Ok((&input[..input.len() - rest.len()], rest))
+    }
+}
+
+pub fn map<'a, T, U>(parser: impl Parser<'a, T>, f: impl Fn(T) -> U) -> impl Parser<'a, U> {
+    move |input: &'a str| parser(input).map(|(value, rest)| (f(value), rest))
+}
+
+/// Convert the parsed value with `f`, an error from `f` describes what was expected of the text
+/// `parser` consumed.
+pub fn try_map<'a, T, U, E: fmt::Display>(
+    parser: impl Parser<'a, T>,
+    f: impl Fn(T) -> Result<U, E>,
+) -> impl Parser<'a, U> {
+    move |input: &'a str| {
+        let (value, rest) = parser(input)?;
+        let consumed = &input[..input.len() - rest.len()];
+
+        f(value)
+            .map(|value| (value, rest))
+            .map_err(|e| Failure::cut(consumed, e.to_string()))
+    }
+}
+
+/// `parser` or `None` if it doesn't match.
+pub fn opt<'a, T>(parser: impl Parser<'a, T>) -> impl Parser<'a, Option<T>> {
+    move |input: &'a str| match parser(input) {
+        Ok((value, rest)) => Ok((Some(value), rest)),
+        Err(failure) if !failure.cut => Ok((None, input)),
+        Err(failure) => Err(failure),
+    }
+}
+
+pub fn pair<'a, A, B>(
+    first: impl Parser<'a, A>,
+    second: impl Parser<'a, B>,
+) -> impl Parser<'a, (A, B)> {
+    move |input: &'a str| {
+        let (a, rest) = first(input)?;
+        let (b, rest) = second(rest)?;
+
+        Ok(((a, b), rest))
+    }
+}
+
+/// `first` and `second` with `separator` in between, which is discarded.
+pub fn separated_pair<'a, A, S, B>(
+    first: impl Parser<'a, A>,
+    separator: impl Parser<'a, S>,
+    second: impl Parser<'a, B>,
+) -> impl Parser<'a, (A, B)> {
+    move |input: &'a str| {
+        let (a, rest) = first(input)?;
+        let (_, rest) = separator(rest)?;
+        let (b, rest) = second(rest)?;
+
+        Ok(((a, b), rest))
+    }
+}
+
+/// `parser` after `prefix`, which is discarded.
+pub fn preceded<'a, P, T>(
+    prefix: impl Parser<'a, P>,
+    parser: impl Parser<'a, T>,
+) -> impl Parser<'a, T> {
+    map(pair(prefix, parser), |(_, value)| value)
+}
+
+/// `parser` followed by `suffix`, which is discarded.
+pub fn terminated<'a, T, S>(
+    parser: impl Parser<'a, T>,
+    suffix: impl Parser<'a, S>,
+) -> impl Parser<'a, T> {
+    map(pair(parser, suffix), |(value, _)| value)
+}
+
+/// `parser` between `open` and `close`, e.g. parenthesis.
+pub fn delimited<'a, O, T, C>(
+    open: impl Parser<'a, O>,
+    parser: impl Parser<'a, T>,
+    close: impl Parser<'a, C>,
+) -> impl Parser<'a, T> {
+    preceded(open, terminated(parser, close))
+}
+
+/// A tuple of parsers producing the same type, see [`alt`].
+pub trait Alt<'a, T> {
+    fn choice(&self, input: &'a str) -> Parsed<'a, T>;
+}
+
+macro_rules! define_alt {
+    ($($parser:ident $index:tt),+) => {
+        impl<'a, T, $($parser: Parser<'a, T>),+> Alt<'a, T> for ($($parser,)+) {
+            fn choice(&self, input: &'a str) -> Parsed<'a, T> {
+                let mut expected = vec![];
+                $(
+                    match (self.$index)(input) {
+                        Err(failure) if !failure.cut => expected.push(failure.expected),
+                        result => return result,
+                    }
+                )+
+
+                Err(Failure::backtrack(input, expected.join(" or ")))
+            }
+        }
+    };
+}
+
+define_alt!(A 0, B 1);
+define_alt!(A 0, B 1, C 2);
+define_alt!(A 0, B 1, C 2, D 3);
+define_alt!(A 0, B 1, C 2, D 3, E 4);
+
+/// The first of `alternatives` that matches, e.g. `alt((tag("do()"), tag("don't()")))`.
+pub fn alt<'a, T>(alternatives: impl Alt<'a, T>) -> impl Parser<'a, T> {
+    move |input: &'a str| alternatives.choice(input)
+}
+
+/// One or more `item`s with `separator` in between, e.g. `1,2,3`.
+pub fn separated<'a, T, S>(
+    item: impl Parser<'a, T>,
+    separator: impl Parser<'a, S>,
+) -> impl Parser<'a, Vec<T>> {
+    move |input: &'a str| {
+        let (first, mut rest) = item(input)?;
+        let mut items = vec![first];
+
+        loop {
+            let next = separator(rest).and_then(|(_, after)| item(after));
+            match next {
+                Ok((value, after)) => {
+                    items.push(value);
+                    rest = after;
+                }
+                Err(failure) if !failure.cut => return Ok((items, rest)),
+                Err(failure) => return Err(failure),
+            }
+        }
+    }
+}
+
+/// Replace failures to match `parser` with `expected` for the whole line, e.g. "two location
+/// IDs" rather than a missing space. Invalid values, such as an integer overflowing, are still
+/// reported as they are.
+pub fn context<'a, T>(expected: &'static str, parser: impl Parser<'a, T>) -> impl Parser<'a, T> {
+    move |input: &'a str| {
+        parser(input).map_err(|failure| {
+            if failure.cut {
+                return failure;
+            }
+            let line = input.lines().next().unwrap_or(input);
+
+            Failure::cut(line.trim_end(), expected)
+        })
+    }
+}
+
+/// `item` for every line that isn't blank, each line must be consumed entirely.
+///
+/// Lines are trimmed, so indentation and trailing whitespace don't matter.
+pub fn lines<'a, T>(item: impl Parser<'a, T>) -> impl Parser<'a, Vec<T>> {
+    move |input: &'a str| {
+        let mut items = vec![];
+        for line in input.lines().map(str::trim).filter(|l| !l.is_empty()) {
+            let (value, rest) = item(line)?;
+            if !rest.is_empty() {
+                return Err(Failure::cut(token(rest), "end of line"));
+            }
+            items.push(value);
+        }
+
+        Ok((items, &input[input.len()..]))
+    }
+}
+
+/// `item` for the text up to the next blank line, e.g. the rules before the updates. Blank lines
+/// around the section are skipped.
+pub fn section<'a, T>(item: impl Parser<'a, T>) -> impl Parser<'a, T> {
+    move |input: &'a str| {
+        let start = skip_blank_lines(input);
+        if start.is_empty() {
+            return Err(Failure::backtrack(start, "a section"));
+        }
+
+        let end = start
+            .split_inclusive('\n')
+            .take_while(|l| !l.trim().is_empty())
+            .map(str::len)
+            .sum();
+        let (body, rest) = start.split_at(end);
+
+        let (value, leftover) = item(body)?;
+        let leftover = leftover.trim_start();
+        if !leftover.is_empty() {
+            return Err(Failure::cut(token(leftover), "end of section"));
+        }
+
+        Ok((value, skip_blank_lines(rest)))
+    }
+}
+
+/// A `key` and a `value` separated by `separator`, with optional spaces around the separator,
+/// e.g. `Register A: 729`.
+pub fn key_value<'a, K, V>(
+    key: impl Parser<'a, K>,
+    separator: &'static str,
+    value: impl Parser<'a, V>,
+) -> impl Parser<'a, (K, V)> {
+    separated_pair(key, delimited(spaces0(), tag(separator), spaces0()), value)
+}
+
+/// A grid of characters up to the next blank line, see [`Grid::parse`].
+pub fn grid<'a, T: TryFrom<char>>() -> impl Parser<'a, Grid<T>> {
+    move |input: &'a str| {
+        let mut rest = skip_blank_lines(input);
+        let mut width = None;
+        let mut cells = vec![];
+
+        while let Some(line) = rest
+            .split_inclusive('\n')
+            .next()
+            .filter(|l| !l.trim().is_empty())
+        {
+            rest = &rest[line.len()..];
+            let line = line.trim();
+
+            let row_width = line.chars().count();
+            if *width.get_or_insert(row_width) != row_width {
+                return Err(Failure::cut(
+                    line,
+                    format!("row of width {}", width.unwrap_or_default()),
+                ));
+            }
+
+            for (i, c) in line.char_indices() {
+                let cell = T::try_from(c)
+                    .map_err(|_| Failure::cut(&line[i..i + c.len_utf8()], type_name::<T>()))?;
+                cells.push(cell);
+            }
+        }
+
+        match width {
+            Some(width) => Ok((Grid::from_cells(width, cells), skip_blank_lines(rest))),
+            None => Err(Failure::backtrack(rest, "non empty grid")),
+        }
+    }
+}
+
+/// Every match of `parser` anywhere in the input, skipping over everything else, e.g.
+/// instructions in corrupted memory. Never fails.
+pub fn scan<'a, T>(parser: impl Parser<'a, T>) -> impl Parser<'a, Vec<T>> {
+    move |input: &'a str| {
+        let mut matches = vec![];
+        let mut rest = input;
+
+        while let Some(c) = rest.chars().next() {
+            match parser(rest) {
+                Ok((value, after)) if after.len() < rest.len() => {
+                    matches.push(value);
+                    rest = after;
+                }
+                _ => rest = &rest[c.len_utf8()..],
+            }
+        }
+
+        Ok((matches, rest))
+    }
+}
+
+/// The input after any leading blank lines.
+fn skip_blank_lines(input: &str) -> &str {
+    let blank: usize = input
+        .split_inclusive('\n')
+        .take_while(|l| l.trim().is_empty())
+        .map(str::len)
+        .sum();
+
+    &input[blank..]
+}
+
+/// The text up to the next whitespace, or the next character if it is whitespace, to point
+/// errors at.
+fn token(rest: &str) -> &str {
+    let end = match rest.find(char::is_whitespace) {
+        Some(0) => rest.chars().next().map_or(0, char::len_utf8),
+        Some(end) => end,
+        None => rest.len(),
+    };
+
+    &rest[..end]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        alt, complete, context, delimited, grid, integer, key_value, lines, map, opt, pair,
+        preceded, scan, section, separated, separated_pair, spaces1, tag, try_map, unsigned,
+    };
+    use crate::grid::Grid;
+    use crate::ParseError;
+
+    const INPUT: &str = r#"
+Register A: 729
+Register B: -3
+
+0,1,5,4
+"#;
+
+    #[test]
+    fn test_integer() {
+        assert_eq!(complete("-12", integer::<i64>()), Ok(-12));
+        assert_eq!(complete("+7", integer::<i64>()), Ok(7));
+        assert_eq!(
+            complete("256", integer::<u8>()),
+            Err(ParseError::new(1, 1, "256", "u8"))
+        );
+        assert_eq!(
+            complete("1x9", integer::<u8>()),
+            Err(ParseError::new(1, 1, "1x9", "u8"))
+        );
+        assert_eq!(
+            complete("-1", unsigned::<i8>()),
+            Err(ParseError::new(1, 1, "-1", "i8"))
+        );
+        assert_eq!(
+            complete("12 13", integer::<u8>()),
+            Err(ParseError::new(1, 4, "13", "end of input"))
+        );
+    }
+
+    #[test]
+    fn test_sections() {
+        let register = key_value(
+            preceded(tag("Register "), alt((tag("A"), tag("B")))),
+            ":",
+            integer::<i64>(),
+        );
+        let even = try_map(separated(unsigned::<u8>(), tag(",")), |p| {
+            if p.len() % 2 == 0 {
+                Ok(p)
+            } else {
+                Err("an even number of values")
+            }
+        });
+
+        let (registers, program) =
+            complete(INPUT, pair(section(lines(&register)), section(&even))).unwrap();
+        assert_eq!(registers, vec![("A", 729), ("B", -3)]);
+        assert_eq!(program, vec![0, 1, 5, 4]);
+
+        assert_eq!(
+            complete("Register A 7", lines(&register)),
+            Err(ParseError::new(1, 12, "7", "`:`"))
+        );
+        assert_eq!(
+            complete("0,1,5", &even),
+            Err(ParseError::new(1, 1, "0,1,5", "an even number of values"))
+        );
+    }
+
+    #[test]
+    fn test_alt() {
+        let bit = alt((map(tag("on"), |_| true), map(tag("off"), |_| false)));
+        assert_eq!(complete("off", &bit), Ok(false));
+        assert_eq!(
+            complete("of", &bit),
+            Err(ParseError::new(1, 1, "of", "`on` or `off`"))
+        );
+
+        let maybe = opt(delimited(tag("("), integer::<i32>(), tag(")")));
+        assert_eq!(complete("(-4)", &maybe), Ok(Some(-4)));
+        assert_eq!(complete("", &maybe), Ok(None));
+    }
+
+    #[test]
+    fn test_context() {
+        let ids = lines(context(
+            "two IDs",
+            separated_pair(integer::<i64>(), spaces1(), integer::<i64>()),
+        ));
+
+        assert_eq!(complete("1 2\n3  4", &ids), Ok(vec![(1, 2), (3, 4)]));
+        assert_eq!(
+            complete("1 2\n 3\n", &ids),
+            Err(ParseError::new(2, 2, "3", "two IDs"))
+        );
+        assert_eq!(
+            complete("1 2\n3 -", &ids),
+            Err(ParseError::new(2, 3, "-", "i64"))
+        );
+    }
+
+    #[test]
+    fn test_grid() {
+        let (first, second) =
+            complete("\n#.\n.#\n\n...\n", pair(grid::<char>(), grid::<char>())).unwrap();
+
+        assert_eq!(first, Grid::from_cells(2, vec!['#', '.', '.', '#']));
+        assert_eq!((second.width(), second.height()), (3, 1));
+    }
+
+    #[test]
+    fn test_scan() {
+        let mul = delimited(
+            tag("mul("),
+            separated_pair(unsigned::<u32>(), tag(","), unsigned::<u32>()),
+            tag(")"),
+        );
+
+        assert_eq!(
+            complete(
+                "xmul(2,4)%mul[3,7]mul(5,5)mul(4*mul(1x,2)mul(11,8)",
+                scan(mul)
+            ),
+            Ok(vec![(2, 4), (5, 5), (11, 8)])
+        );
+    }
+}
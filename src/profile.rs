@@ -0,0 +1,288 @@
+use std::fmt::Write;
+use std::hint::black_box;
+use std::time::Duration;
+
+use crate::bench::{format_duration, time};
+use crate::{DynSolution, ParseError, Part};
+
+/// Heap usage of one phase.
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq)]
+pub struct Allocs {
+    /// Calls to `alloc` and `realloc`, from every thread.
+    pub count: u64,
+    /// The most bytes allocated at once during the phase, on top of what was allocated before it.
+    pub peak_bytes: usize,
+}
+
+/// A single run of one phase.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct Phase {
+    pub duration: Duration,
+    /// `None` without the `count-alloc` feature.
+    pub allocs: Option<Allocs>,
+}
+
+/// Where the time and memory of one day goes, parsing is measured separately from solving.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct Profile {
+    pub day: u8,
+    pub parse: Phase,
+    pub part_one: Phase,
+    pub part_two: Phase,
+}
+
+impl Profile {
+    pub fn phases(&self) -> [(&'static str, &Phase); 3] {
+        [
+            ("parse", &self.parse),
+            ("part_one", &self.part_one),
+            ("part_two", &self.part_two),
+        ]
+    }
+}
+
+/// Whether allocations are counted, i.e. the `count-alloc` feature is enabled.
+pub const fn counts_allocations() -> bool {
+    cfg!(feature = "count-alloc")
+}
+
+/// Run `closure` once, measuring how long it took and what it allocated.
+///
+/// Measurements don't nest, the peak of an outer phase is reset by an inner one.
+pub fn measure<R>(closure: impl FnOnce() -> R) -> (R, Phase) {
+    #[cfg(feature = "count-alloc")]
+    {
+        let start = counting::start();
+        let (result, duration) = time(closure);
+        let allocs = counting::since(start);
+
+        (
+            result,
+            Phase {
+                duration,
+                allocs: Some(allocs),
+            },
+        )
+    }
+
+    #[cfg(not(feature = "count-alloc"))]
+    {
+        let (result, duration) = time(closure);
+
+        (
+            result,
+            Phase {
+                duration,
+                allocs: None,
+            },
+        )
+    }
+}
+
+/// Parse `input` and solve both parts of `solution` once, measuring each phase.
+pub fn profile(day: u8, solution: &dyn DynSolution, input: &str) -> Result<Profile, ParseError> {
+    let (parsed, parse) = measure(|| solution.parse_input(input));
+    let parsed = parsed?;
+
+    let solve = |part| measure(|| black_box(solution.solve_part(&parsed, part))).1;
+    let part_one = solve(Part::One);
+    let part_two = solve(Part::Two);
+
+    Ok(Profile {
+        day,
+        parse,
+        part_one,
+        part_two,
+    })
+}
+
+/// Render profiles as a markdown table with one row per day and phase, allocation columns are
+/// `-` when they weren't counted.
+pub fn to_markdown(profiles: &[Profile]) -> String {
+    let mut out = String::from(
+        "| Day | Phase | Time | Allocations | Peak |\n|----:|:------|-----:|------------:|-----:|\n",
+    );
+
+    for profile in profiles {
+        for (name, phase) in profile.phases() {
+            let (count, peak) = match phase.allocs {
+                Some(allocs) => (allocs.count.to_string(), format_bytes(allocs.peak_bytes)),
+                None => ("-".to_owned(), "-".to_owned()),
+            };
+            writeln!(
+                out,
+                "| {:02} | {name} | {} | {count} | {peak} |",
+                profile.day,
+                format_duration(phase.duration)
+            )
+            .unwrap();
+        }
+    }
+
+    out
+}
+
+pub fn format_bytes(bytes: usize) -> String {
+    const KIB: f64 = 1024.0;
+
+    let b = bytes as f64;
+    if b < KIB {
+        format!("{bytes}B")
+    } else if b < KIB * KIB {
+        format!("{:.2}KiB", b / KIB)
+    } else if b < KIB * KIB * KIB {
+        format!("{:.2}MiB", b / (KIB * KIB))
+    } else {
+        format!("{:.2}GiB", b / (KIB * KIB * KIB))
+    }
+}
+
+/// A global allocator that forwards to the system allocator while counting calls and tracking
+/// the number of bytes in use.
+#[cfg(feature = "count-alloc")]
+mod counting {
+    use std::alloc::{GlobalAlloc, Layout, System};
+    use std::sync::atomic::{AtomicU64, AtomicUsize, Ordering};
+
+    use super::Allocs;
+
+    #[global_allocator]
+    static ALLOCATOR: Counting = Counting;
+
+    static COUNT: AtomicU64 = AtomicU64::new(0);
+    static CURRENT: AtomicUsize = AtomicUsize::new(0);
+    static PEAK: AtomicUsize = AtomicUsize::new(0);
+
+    struct Counting;
+
+    // SAFETY: Every call is forwarded unchanged to `System`, which upholds the `GlobalAlloc`
+    // contract. The counters are updated afterwards and never affect the returned pointers.
+    unsafe impl GlobalAlloc for Counting {
+        unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+            let ptr = System.alloc(layout);
+            if !ptr.is_null() {
+                grow(layout.size());
+            }
+
+            ptr
+        }
+
+        unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+            let ptr = System.alloc_zeroed(layout);
+            if !ptr.is_null() {
+                grow(layout.size());
+            }
+
+            ptr
+        }
+
+        unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+            System.dealloc(ptr, layout);
+            CURRENT.fetch_sub(layout.size(), Ordering::Relaxed);
+        }
+
+        unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+            let new = System.realloc(ptr, layout, new_size);
+            if !new.is_null() {
+                CURRENT.fetch_sub(layout.size(), Ordering::Relaxed);
+                grow(new_size);
+            }
+
+            new
+        }
+    }
+
+    fn grow(size: usize) {
+        COUNT.fetch_add(1, Ordering::Relaxed);
+        let current = CURRENT.fetch_add(size, Ordering::Relaxed) + size;
+        PEAK.fetch_max(current, Ordering::Relaxed);
+    }
+
+    /// Reset the peak and return the counters to pass to [`since`].
+    pub fn start() -> (u64, usize) {
+        let current = CURRENT.load(Ordering::Relaxed);
+        PEAK.store(current, Ordering::Relaxed);
+
+        (COUNT.load(Ordering::Relaxed), current)
+    }
+
+    pub fn since((count, current): (u64, usize)) -> Allocs {
+        Allocs {
+            count: COUNT.load(Ordering::Relaxed) - count,
+            peak_bytes: PEAK.load(Ordering::Relaxed).saturating_sub(current),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::{
+        counts_allocations, format_bytes, measure, profile, to_markdown, Allocs, Phase, Profile,
+    };
+    use crate::day01::Day01;
+
+    #[test]
+    fn test_profile() {
+        let report = profile(1, &Day01, "3   4\n4   3\n").unwrap();
+
+        assert_eq!(report.day, 1);
+        assert_eq!(report.parse.allocs.is_some(), counts_allocations());
+        if let Some(allocs) = report.parse.allocs {
+            assert!(allocs.count > 0);
+            assert!(allocs.peak_bytes > 0);
+        }
+        assert!(profile(1, &Day01, "3   x\n").is_err());
+    }
+
+    #[test]
+    fn test_measure() {
+        let (v, phase) = measure(|| Vec::<u8>::with_capacity(4096));
+
+        assert_eq!(v.capacity(), 4096);
+        // Other tests allocate concurrently, so these are lower bounds
+        if let Some(allocs) = phase.allocs {
+            assert!(allocs.count >= 1);
+            assert!(allocs.peak_bytes >= 4096);
+        }
+    }
+
+    #[test]
+    fn test_markdown() {
+        let phase = Phase {
+            duration: Duration::from_micros(1500),
+            allocs: Some(Allocs {
+                count: 12,
+                peak_bytes: 3 * 1024 * 1024,
+            }),
+        };
+        let mut profiles = [Profile {
+            day: 8,
+            parse: phase,
+            part_one: phase,
+            part_two: phase,
+        }];
+
+        let markdown = to_markdown(&profiles);
+        assert_eq!(markdown.lines().count(), 5);
+        assert_eq!(
+            markdown.lines().nth(2),
+            Some("| 08 | parse | 1.50ms | 12 | 3.00MiB |")
+        );
+
+        profiles[0].part_two.allocs = None;
+        assert_eq!(
+            to_markdown(&profiles).lines().last(),
+            Some("| 08 | part_two | 1.50ms | - | - |")
+        );
+    }
+
+    #[test]
+    fn test_format_bytes() {
+        assert_eq!(format_bytes(0), "0B");
+        assert_eq!(format_bytes(1023), "1023B");
+        assert_eq!(format_bytes(1536), "1.50KiB");
+        assert_eq!(format_bytes(5 * 1024 * 1024 * 1024), "5.00GiB");
+    }
+}
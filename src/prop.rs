@@ -0,0 +1,338 @@
+use std::fmt::{self, Debug};
+use std::ops::RangeInclusive;
+
+use crate::rng::Rng;
+
+/// Random values for property tests, together with simpler variations of a value used to
+/// shrink failing cases.
+pub trait Generator {
+    type Value: Clone + Debug;
+
+    fn generate(&self, rng: &mut Rng) -> Self::Value;
+
+    /// Simpler variations of `value`, the most aggressive first. Empty when `value` is as simple
+    /// as it gets.
+    fn shrink(&self, value: &Self::Value) -> Vec<Self::Value>;
+}
+
+/// Integers in `range`, shrinking towards the value closest to zero.
+///
+/// **Note:** Panics if `range` is empty
+pub fn int(range: RangeInclusive<i64>) -> Int {
+    assert!(!range.is_empty(), "Range must not be empty");
+
+    Int { range }
+}
+
+#[derive(Debug, Clone)]
+pub struct Int {
+    range: RangeInclusive<i64>,
+}
+
+impl Generator for Int {
+    type Value = i64;
+
+    fn generate(&self, rng: &mut Rng) -> i64 {
+        rng.range(self.range.clone())
+    }
+
+    fn shrink(&self, value: &i64) -> Vec<i64> {
+        let target = 0.clamp(*self.range.start(), *self.range.end());
+        if *value == target {
+            return vec![];
+        }
+
+        let halfway = value - (value - target) / 2;
+        let closer = value - (value - target).signum();
+        let mut candidates = vec![target, halfway, closer];
+        // Halving a distance of one doesn't move
+        candidates.retain(|c| c != value);
+        candidates.dedup();
+
+        candidates
+    }
+}
+
+/// Vectors of `element` with a length in `len`, shrinking by dropping elements and then by
+/// shrinking the elements that are left.
+///
+/// **Note:** Panics if `len` is empty
+pub fn vec<G: Generator>(element: G, len: RangeInclusive<usize>) -> VecOf<G> {
+    assert!(!len.is_empty(), "Length range must not be empty");
+
+    VecOf { element, len }
+}
+
+#[derive(Debug, Clone)]
+pub struct VecOf<G> {
+    element: G,
+    len: RangeInclusive<usize>,
+}
+
+impl<G: Generator> Generator for VecOf<G> {
+    type Value = Vec<G::Value>;
+
+    fn generate(&self, rng: &mut Rng) -> Self::Value {
+        let (min, max) = (*self.len.start(), *self.len.end());
+        let len = min + rng.index(max - min + 1);
+
+        (0..len).map(|_| self.element.generate(rng)).collect()
+    }
+
+    fn shrink(&self, value: &Self::Value) -> Vec<Self::Value> {
+        let min = *self.len.start();
+        let mut candidates = vec![];
+
+        if value.len() / 2 >= min && value.len() > 1 {
+            candidates.push(value[..value.len() / 2].to_vec());
+            candidates.push(value[value.len() / 2..].to_vec());
+        }
+        if value.len() > min {
+            candidates.extend((0..value.len()).map(|i| {
+                let mut shorter = value.clone();
+                shorter.remove(i);
+                shorter
+            }));
+        }
+        for (i, element) in value.iter().enumerate() {
+            candidates.extend(self.element.shrink(element).into_iter().map(|e| {
+                let mut simpler = value.clone();
+                simpler[i] = e;
+                simpler
+            }));
+        }
+
+        candidates
+    }
+}
+
+/// Values built by `generate` and shrunk by `shrink`, for inputs the other generators can't
+/// describe.
+pub fn from_fn<T, F, S>(generate: F, shrink: S) -> FromFn<F, S>
+where
+    T: Clone + Debug,
+    F: Fn(&mut Rng) -> T,
+    S: Fn(&T) -> Vec<T>,
+{
+    FromFn { generate, shrink }
+}
+
+pub struct FromFn<F, S> {
+    generate: F,
+    shrink: S,
+}
+
+impl<T, F, S> Generator for FromFn<F, S>
+where
+    T: Clone + Debug,
+    F: Fn(&mut Rng) -> T,
+    S: Fn(&T) -> Vec<T>,
+{
+    type Value = T;
+
+    fn generate(&self, rng: &mut Rng) -> T {
+        (self.generate)(rng)
+    }
+
+    fn shrink(&self, value: &T) -> Vec<T> {
+        (self.shrink)(value)
+    }
+}
+
+/// Implement [`Generator`] for a tuple of generators, shrinking one component at a time.
+macro_rules! impl_generator_tuple {
+    ($($g:ident $i:tt),+) => {
+        impl<$($g: Generator),+> Generator for ($($g,)+) {
+            type Value = ($($g::Value,)+);
+
+            fn generate(&self, rng: &mut Rng) -> Self::Value {
+                ($(self.$i.generate(rng),)+)
+            }
+
+            fn shrink(&self, value: &Self::Value) -> Vec<Self::Value> {
+                let mut candidates = vec![];
+                $(
+                    candidates.extend(self.$i.shrink(&value.$i).into_iter().map(|c| {
+                        let mut simpler = value.clone();
+                        simpler.$i = c;
+                        simpler
+                    }));
+                )+
+
+                candidates
+            }
+        }
+    };
+}
+
+impl_generator_tuple!(A 0, B 1);
+impl_generator_tuple!(A 0, B 1, C 2);
+impl_generator_tuple!(A 0, B 1, C 2, D 3);
+
+/// How many cases to try and how hard to shrink.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Config {
+    pub cases: usize,
+    pub seed: u64,
+    /// The most simplifications accepted before settling for the current counterexample.
+    pub max_shrinks: usize,
+}
+
+impl Config {
+    /// The default config trying `cases` inputs.
+    pub fn cases(cases: usize) -> Self {
+        Self {
+            cases,
+            ..Self::default()
+        }
+    }
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Self {
+            cases: 1000,
+            seed: 0,
+            max_shrinks: 1000,
+        }
+    }
+}
+
+/// A counterexample to a property.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Failure<T> {
+    /// Generating from `Rng::new(seed)` reproduces `original`.
+    pub seed: u64,
+    pub original: T,
+    /// The simplest failing value found by shrinking `original`.
+    pub minimal: T,
+    pub shrinks: usize,
+}
+
+impl<T: Debug> fmt::Display for Failure<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "property failed for {:?} (seed {:#x}), shrunk {} times from {:?}",
+            self.minimal, self.seed, self.shrinks, self.original
+        )
+    }
+}
+
+/// Check `property` against `config.cases` values from `generator`. The first failing value is
+/// shrunk greedily, taking the first simpler variation that still fails until none do.
+pub fn check<G: Generator>(
+    config: Config,
+    generator: &G,
+    property: impl Fn(&G::Value) -> bool,
+) -> Result<(), Failure<G::Value>> {
+    let mut seeds = Rng::new(config.seed);
+
+    for _ in 0..config.cases {
+        let seed = seeds.next_u64();
+        let original = generator.generate(&mut Rng::new(seed));
+        if property(&original) {
+            continue;
+        }
+
+        let mut minimal = original.clone();
+        let mut shrinks = 0;
+        while shrinks < config.max_shrinks {
+            match generator
+                .shrink(&minimal)
+                .into_iter()
+                .find(|c| !property(c))
+            {
+                Some(simpler) => {
+                    minimal = simpler;
+                    shrinks += 1;
+                }
+                None => break,
+            }
+        }
+
+        return Err(Failure {
+            seed,
+            original,
+            minimal,
+            shrinks,
+        });
+    }
+
+    Ok(())
+}
+
+/// [`check`] for use in tests.
+///
+/// **Note:** Panics with the shrunk counterexample if `property` fails
+pub fn assert_holds<G: Generator>(
+    config: Config,
+    generator: &G,
+    property: impl Fn(&G::Value) -> bool,
+) {
+    if let Err(failure) = check(config, generator, property) {
+        panic!("{failure}");
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{assert_holds, check, from_fn, int, vec, Config, Generator};
+    use crate::rng::Rng;
+
+    #[test]
+    fn test_check() {
+        assert_holds(Config::default(), &int(-5..=5), |x| x.abs() <= 5);
+
+        let failure = check(Config::default(), &int(0..=1000), |x| *x < 50).unwrap_err();
+        assert_eq!(failure.minimal, 50);
+        assert!(failure.original >= 50);
+        assert_eq!(
+            int(0..=1000).generate(&mut Rng::new(failure.seed)),
+            failure.original
+        );
+
+        // Shrinks towards the bound closest to zero
+        let failure = check(Config::default(), &int(-100..=-10), |x| *x > -20).unwrap_err();
+        assert_eq!(failure.minimal, -20);
+
+        assert_eq!(int(-5..=5).shrink(&-1), vec![0]);
+        assert!(int(-5..=5).shrink(&0).is_empty());
+    }
+
+    #[test]
+    fn test_shrink_vec() {
+        let failure = check(Config::default(), &vec(int(0..=100), 0..=20), |v| {
+            v.iter().all(|x| *x <= 10)
+        })
+        .unwrap_err();
+        assert_eq!(failure.minimal, vec![11]);
+
+        let failure = check(Config::default(), &vec(int(0..=9), 3..=10), |v| {
+            v.iter().sum::<i64>() < 20
+        })
+        .unwrap_err();
+        assert!(failure.minimal.len() >= 3);
+        assert_eq!(failure.minimal.iter().sum::<i64>(), 20);
+    }
+
+    #[test]
+    fn test_shrink_tuple() {
+        let pair = (int(0..=50), vec(int(0..=50), 1..=5));
+        let failure = check(Config::default(), &pair, |(a, v)| *a < 30 || v[0] < 40).unwrap_err();
+
+        assert_eq!(failure.minimal, (30, vec![40]));
+    }
+
+    #[test]
+    fn test_from_fn() {
+        let even = from_fn(
+            |rng| 2 * rng.range(0..=100),
+            |x: &i64| if *x > 0 { vec![x - 2] } else { vec![] },
+        );
+        let failure = check(Config::cases(100), &even, |x| *x < 31).unwrap_err();
+
+        assert_eq!(failure.minimal, 32);
+        assert_eq!(check(Config::cases(100), &even, |x| x % 2 == 0), Ok(()));
+    }
+}
@@ -0,0 +1,276 @@
+use std::collections::HashMap;
+
+use crate::grid::Grid;
+use crate::math::{Direction, Vector2};
+
+/// A disjoint-set forest over the elements `0..len`, with path compression and union by size.
+///
+/// Elements can be added at any time with [`UnionFind::push`], which makes it suitable for
+/// puzzles where connections are added one at a time.
+#[derive(Debug, Clone, Default)]
+pub struct UnionFind {
+    parent: Vec<usize>,
+    size: Vec<usize>,
+    sets: usize,
+}
+
+impl UnionFind {
+    /// `len` elements, each in a set of its own.
+    pub fn new(len: usize) -> Self {
+        Self {
+            parent: (0..len).collect(),
+            size: vec![1; len],
+            sets: len,
+        }
+    }
+
+    /// Add a new element in a set of its own and return it.
+    pub fn push(&mut self) -> usize {
+        let element = self.parent.len();
+        self.parent.push(element);
+        self.size.push(1);
+        self.sets += 1;
+
+        element
+    }
+
+    /// The number of elements.
+    pub fn len(&self) -> usize {
+        self.parent.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.parent.is_empty()
+    }
+
+    /// The number of disjoint sets.
+    pub fn set_count(&self) -> usize {
+        self.sets
+    }
+
+    /// The representative of the set containing `element`.
+    ///
+    /// **Note:** Panics if `element` is out of bounds
+    pub fn find(&mut self, mut element: usize) -> usize {
+        while self.parent[element] != element {
+            // Path halving
+            let grandparent = self.parent[self.parent[element]];
+            self.parent[element] = grandparent;
+            element = grandparent;
+        }
+
+        element
+    }
+
+    /// Merge the sets containing `a` and `b`, `false` if they were already the same set.
+    pub fn union(&mut self, a: usize, b: usize) -> bool {
+        let (mut a, mut b) = (self.find(a), self.find(b));
+        if a == b {
+            return false;
+        }
+
+        if self.size[a] < self.size[b] {
+            (a, b) = (b, a);
+        }
+        self.parent[b] = a;
+        self.size[a] += self.size[b];
+        self.sets -= 1;
+
+        true
+    }
+
+    pub fn connected(&mut self, a: usize, b: usize) -> bool {
+        self.find(a) == self.find(b)
+    }
+
+    /// The size of the set containing `element`.
+    pub fn size_of(&mut self, element: usize) -> usize {
+        let root = self.find(element);
+
+        self.size[root]
+    }
+}
+
+/// A connected group of cells in a grid.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Region {
+    /// The cells of the region in row major order.
+    pub cells: Vec<Vector2<isize>>,
+    /// The top left corner of the bounding box.
+    pub min: Vector2<isize>,
+    /// The bottom right corner of the bounding box, inclusive.
+    pub max: Vector2<isize>,
+    perimeter: usize,
+}
+
+impl Region {
+    pub fn area(&self) -> usize {
+        self.cells.len()
+    }
+
+    /// The number of cell edges bordering a cell outside the region, or the edge of the grid.
+    pub fn perimeter(&self) -> usize {
+        self.perimeter
+    }
+}
+
+/// The regions of a grid and the region of every cell.
+#[derive(Debug, Clone)]
+pub struct Labels {
+    labels: Grid<Option<usize>>,
+    regions: Vec<Region>,
+}
+
+impl Labels {
+    /// The index of the region containing `position`, `None` for cells outside every region.
+    pub fn region_of(&self, position: Vector2<isize>) -> Option<usize> {
+        self.labels.get(position).copied().flatten()
+    }
+
+    /// All regions, ordered by their first cell in row major order.
+    pub fn regions(&self) -> &[Region] {
+        &self.regions
+    }
+}
+
+/// Label regions of orthogonally adjacent cells where `connected(a, b)` holds for neighboring
+/// cells `a` and `b`, e.g. `|a, b| a == b` for regions of the same plant. Every cell belongs to
+/// a region.
+///
+/// `connected` must be symmetric.
+pub fn label<T>(grid: &Grid<T>, connected: impl Fn(&T, &T) -> bool) -> Labels {
+    label_where(grid, |_| true, connected)
+}
+
+/// Like [`label`] but only cells matching `include` are part of a region, e.g. the open cells of
+/// a maze.
+pub fn label_where<T>(
+    grid: &Grid<T>,
+    include: impl Fn(&T) -> bool,
+    connected: impl Fn(&T, &T) -> bool,
+) -> Labels {
+    let width = grid.width();
+    let index = |p: Vector2<isize>| p.y as usize * width + p.x as usize;
+    let mut sets = UnionFind::new(width * grid.height());
+
+    for (position, cell) in grid.iter().filter(|(_, c)| include(c)) {
+        // Looking right and down is enough to see every pair once
+        for direction in [Direction::Right, Direction::Down] {
+            let next = position + direction.offset();
+            if grid
+                .get(next)
+                .is_some_and(|n| include(n) && connected(cell, n))
+            {
+                sets.union(index(position), index(next));
+            }
+        }
+    }
+
+    let mut roots = HashMap::new();
+    let mut regions: Vec<Region> = vec![];
+    let mut labels = grid.map(|_| None);
+    for (position, _) in grid.iter().filter(|(_, c)| include(c)) {
+        let root = sets.find(index(position));
+        let label = *roots.entry(root).or_insert_with(|| {
+            regions.push(Region {
+                cells: vec![],
+                min: position,
+                max: position,
+                perimeter: 0,
+            });
+
+            regions.len() - 1
+        });
+
+        let region = &mut regions[label];
+        region.cells.push(position);
+        region.min = Vector2::new(region.min.x.min(position.x), region.min.y.min(position.y));
+        region.max = Vector2::new(region.max.x.max(position.x), region.max.y.max(position.y));
+        labels[position] = Some(label);
+    }
+
+    for (label, region) in regions.iter_mut().enumerate() {
+        region.perimeter = region
+            .cells
+            .iter()
+            .flat_map(|p| Direction::CARDINAL.map(|d| *p + d.offset()))
+            .filter(|n| labels.get(*n).copied().flatten() != Some(label))
+            .count();
+    }
+
+    Labels { labels, regions }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{label, label_where, UnionFind};
+    use crate::grid::Grid;
+    use crate::math::Vector2;
+
+    const GARDEN: &str = r#"
+AAAA
+BBCD
+BBCC
+EEEC
+"#;
+
+    #[test]
+    fn test_union_find() {
+        let mut sets = UnionFind::new(4);
+        assert_eq!(sets.set_count(), 4);
+
+        assert!(sets.union(0, 1));
+        assert!(sets.union(2, 3));
+        assert!(!sets.union(1, 0));
+        assert!(sets.connected(0, 1));
+        assert!(!sets.connected(1, 2));
+        assert_eq!(sets.set_count(), 2);
+
+        // Incrementally add an element bridging the two sets
+        let bridge = sets.push();
+        assert_eq!((bridge, sets.len(), sets.set_count()), (4, 5, 3));
+        sets.union(bridge, 0);
+        sets.union(3, bridge);
+        assert!(sets.connected(1, 2));
+        assert_eq!(sets.size_of(2), 5);
+        assert_eq!(sets.set_count(), 1);
+    }
+
+    #[test]
+    fn test_label() {
+        let grid = Grid::<char>::parse(GARDEN).unwrap();
+        let labels = label(&grid, |a, b| a == b);
+
+        let summary: Vec<_> = labels
+            .regions()
+            .iter()
+            .map(|r| (grid[r.cells[0]], r.area(), r.perimeter()))
+            .collect();
+        assert_eq!(
+            summary,
+            vec![
+                ('A', 4, 10),
+                ('B', 4, 8),
+                ('C', 4, 10),
+                ('D', 1, 4),
+                ('E', 3, 8)
+            ]
+        );
+
+        let c = &labels.regions()[2];
+        assert_eq!((c.min, c.max), (Vector2::new(2, 1), Vector2::new(3, 3)));
+        assert_eq!(labels.region_of(Vector2::new(3, 3)), Some(2));
+        assert_eq!(labels.region_of(Vector2::new(4, 0)), None);
+    }
+
+    #[test]
+    fn test_label_where() {
+        let grid = Grid::<char>::parse("..#.\n###.\n.#..").unwrap();
+        let labels = label_where(&grid, |c| *c == '.', |_, _| true);
+
+        let areas: Vec<_> = labels.regions().iter().map(|r| r.area()).collect();
+        assert_eq!(areas, vec![2, 4, 1]);
+        assert_eq!(labels.region_of(Vector2::new(2, 0)), None);
+        assert_eq!(labels.region_of(Vector2::new(2, 2)), Some(1));
+    }
+}
@@ -0,0 +1,510 @@
+use std::cmp::Ordering;
+use std::collections::{BinaryHeap, HashMap, HashSet, VecDeque};
+use std::hash::Hash;
+use std::marker::PhantomData;
+
+use crate::grid::Grid;
+use crate::math::Vector2;
+
+/// A directed graph explored lazily from a start node.
+pub trait Graph {
+    type Node: Clone + Eq + Hash;
+
+    /// The nodes reachable from `node` in a single step, with the cost of that step.
+    fn neighbors(&self, node: &Self::Node) -> impl Iterator<Item = (Self::Node, u64)>;
+}
+
+/// A graph defined by a closure returning the neighbors of a node, see [`from_fn`].
+pub struct FnGraph<N, F, I> {
+    neighbors: F,
+    _marker: PhantomData<fn(&N) -> I>,
+}
+
+/// A graph whose neighbors, with costs, are given by `neighbors`.
+pub fn from_fn<N, F, I>(neighbors: F) -> FnGraph<N, F, I>
+where
+    F: Fn(&N) -> I,
+    I: IntoIterator<Item = (N, u64)>,
+{
+    FnGraph {
+        neighbors,
+        _marker: PhantomData,
+    }
+}
+
+impl<N, F, I> Graph for FnGraph<N, F, I>
+where
+    N: Clone + Eq + Hash,
+    F: Fn(&N) -> I,
+    I: IntoIterator<Item = (N, u64)>,
+{
+    type Node = N;
+
+    fn neighbors(&self, node: &Self::Node) -> impl Iterator<Item = (Self::Node, u64)> {
+        (self.neighbors)(node).into_iter()
+    }
+}
+
+/// A grid where every step to an orthogonal neighbor costs 1.
+///
+/// Whether a step is possible is decided by `can_move(from, to)` with the cells on either side.
+pub struct GridGraph<'a, T, F> {
+    grid: &'a Grid<T>,
+    can_move: F,
+}
+
+impl<'a, T, F> GridGraph<'a, T, F>
+where
+    F: Fn(&T, &T) -> bool,
+{
+    pub fn new(grid: &'a Grid<T>, can_move: F) -> Self {
+        Self { grid, can_move }
+    }
+}
+
+impl<T, F> Graph for GridGraph<'_, T, F>
+where
+    F: Fn(&T, &T) -> bool,
+{
+    type Node = Vector2<isize>;
+
+    fn neighbors(&self, node: &Self::Node) -> impl Iterator<Item = (Self::Node, u64)> {
+        let node = *node;
+        let from = &self.grid[node];
+
+        self.grid
+            .neighbors4(node)
+            .filter(move |next| (self.can_move)(from, &self.grid[*next]))
+            .map(|next| (next, 1))
+    }
+}
+
+/// A path from the start node to a goal, both included.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Path<N> {
+    pub nodes: Vec<N>,
+    pub cost: u64,
+}
+
+impl<N> Path<N> {
+    pub fn goal(&self) -> &N {
+        self.nodes.last().expect("Paths contain at least the start")
+    }
+}
+
+/// Breadth first search for the path with the fewest steps to a node matching `is_goal`, the
+/// costs of the graph are ignored and the cost of the path is its number of steps.
+pub fn bfs<G: Graph>(
+    graph: &G,
+    start: G::Node,
+    is_goal: impl Fn(&G::Node) -> bool,
+) -> Option<Path<G::Node>> {
+    let mut parents = HashMap::new();
+    let mut seen = HashSet::from([start.clone()]);
+    let mut queue = VecDeque::from([(start, 0)]);
+
+    while let Some((node, steps)) = queue.pop_front() {
+        if is_goal(&node) {
+            return Some(Path {
+                nodes: reconstruct(&parents, node),
+                cost: steps,
+            });
+        }
+
+        for (next, _) in graph.neighbors(&node) {
+            if seen.insert(next.clone()) {
+                parents.insert(next.clone(), node.clone());
+                queue.push_back((next, steps + 1));
+            }
+        }
+    }
+
+    None
+}
+
+/// Dijkstra's algorithm, the cheapest path to a node matching `is_goal`.
+pub fn dijkstra<G: Graph>(
+    graph: &G,
+    start: G::Node,
+    is_goal: impl Fn(&G::Node) -> bool,
+) -> Option<Path<G::Node>> {
+    astar(graph, start, is_goal, |_| 0)
+}
+
+/// A* search, the cheapest path to a node matching `is_goal`.
+///
+/// `heuristic` estimates the remaining cost to a goal, it must never overestimate and never
+/// decrease by more than the cost of a step for the result to be the cheapest path.
+pub fn astar<G: Graph>(
+    graph: &G,
+    start: G::Node,
+    is_goal: impl Fn(&G::Node) -> bool,
+    heuristic: impl Fn(&G::Node) -> u64,
+) -> Option<Path<G::Node>> {
+    let mut costs = HashMap::from([(start.clone(), 0)]);
+    let mut parents = HashMap::new();
+    let mut heap = BinaryHeap::from([State {
+        priority: heuristic(&start),
+        cost: 0,
+        node: start,
+    }]);
+
+    while let Some(State { cost, node, .. }) = heap.pop() {
+        if costs.get(&node).is_some_and(|c| cost > *c) {
+            continue;
+        }
+        if is_goal(&node) {
+            return Some(Path {
+                nodes: reconstruct(&parents, node),
+                cost,
+            });
+        }
+
+        for (next, step) in graph.neighbors(&node) {
+            let next_cost = cost + step;
+            if costs.get(&next).is_none_or(|c| next_cost < *c) {
+                costs.insert(next.clone(), next_cost);
+                parents.insert(next.clone(), node.clone());
+                heap.push(State {
+                    priority: next_cost + heuristic(&next),
+                    cost: next_cost,
+                    node: next,
+                });
+            }
+        }
+    }
+
+    None
+}
+
+/// The cheapest cost, and a cheapest path, from a start node to every reachable node.
+#[derive(Debug, Clone)]
+pub struct ShortestPaths<N> {
+    costs: HashMap<N, u64>,
+    parents: HashMap<N, N>,
+}
+
+impl<N: Clone + Eq + Hash> ShortestPaths<N> {
+    pub fn cost(&self, node: &N) -> Option<u64> {
+        self.costs.get(node).copied()
+    }
+
+    pub fn path_to(&self, node: &N) -> Option<Path<N>> {
+        let cost = self.cost(node)?;
+
+        Some(Path {
+            nodes: reconstruct(&self.parents, node.clone()),
+            cost,
+        })
+    }
+
+    /// Every reachable node with its cost, in no particular order.
+    pub fn iter(&self) -> impl Iterator<Item = (&N, u64)> {
+        self.costs.iter().map(|(n, c)| (n, *c))
+    }
+}
+
+/// Dijkstra's algorithm from `start` until every reachable node is found.
+pub fn shortest_paths<G: Graph>(graph: &G, start: G::Node) -> ShortestPaths<G::Node> {
+    let mut costs = HashMap::from([(start.clone(), 0)]);
+    let mut parents = HashMap::new();
+    let mut heap = BinaryHeap::from([State {
+        priority: 0,
+        cost: 0,
+        node: start,
+    }]);
+
+    while let Some(State { cost, node, .. }) = heap.pop() {
+        if costs.get(&node).is_some_and(|c| cost > *c) {
+            continue;
+        }
+
+        for (next, step) in graph.neighbors(&node) {
+            let next_cost = cost + step;
+            if costs.get(&next).is_none_or(|c| next_cost < *c) {
+                costs.insert(next.clone(), next_cost);
+                parents.insert(next.clone(), node.clone());
+                heap.push(State {
+                    priority: next_cost,
+                    cost: next_cost,
+                    node: next,
+                });
+            }
+        }
+    }
+
+    ShortestPaths { costs, parents }
+}
+
+/// Every cheapest path from a start node to the goals, stored as a DAG of predecessors.
+#[derive(Debug, Clone)]
+pub struct AllShortestPaths<N> {
+    pub cost: u64,
+    /// The goals reached at `cost`.
+    pub goals: Vec<N>,
+    predecessors: HashMap<N, Vec<N>>,
+}
+
+impl<N: Clone + Eq + Hash> AllShortestPaths<N> {
+    /// Every node that is on at least one cheapest path.
+    pub fn nodes(&self) -> HashSet<N> {
+        let mut nodes: HashSet<N> = self.goals.iter().cloned().collect();
+        let mut stack = self.goals.clone();
+
+        while let Some(node) = stack.pop() {
+            for previous in self.predecessors(&node) {
+                if nodes.insert(previous.clone()) {
+                    stack.push(previous.clone());
+                }
+            }
+        }
+
+        nodes
+    }
+
+    /// The number of distinct cheapest paths.
+    pub fn count(&self) -> u64 {
+        let mut counts = HashMap::new();
+
+        self.goals
+            .iter()
+            .map(|goal| self.count_to(goal, &mut counts))
+            .sum()
+    }
+
+    /// All cheapest paths, there can be exponentially many of them.
+    pub fn paths(&self) -> Vec<Vec<N>> {
+        let mut paths = vec![];
+        for goal in &self.goals {
+            self.collect_paths(vec![goal.clone()], &mut paths);
+        }
+
+        paths
+    }
+
+    fn predecessors(&self, node: &N) -> &[N] {
+        self.predecessors.get(node).map_or(&[], Vec::as_slice)
+    }
+
+    fn count_to(&self, node: &N, counts: &mut HashMap<N, u64>) -> u64 {
+        if let Some(count) = counts.get(node) {
+            return *count;
+        }
+
+        let predecessors = self.predecessors(node);
+        let count = if predecessors.is_empty() {
+            1
+        } else {
+            predecessors.iter().map(|p| self.count_to(p, counts)).sum()
+        };
+        counts.insert(node.clone(), count);
+
+        count
+    }
+
+    fn collect_paths(&self, suffix: Vec<N>, paths: &mut Vec<Vec<N>>) {
+        let predecessors = self.predecessors(&suffix[0]);
+        if predecessors.is_empty() {
+            paths.push(suffix);
+            return;
+        }
+
+        for previous in predecessors {
+            let mut path = Vec::with_capacity(suffix.len() + 1);
+            path.push(previous.clone());
+            path.extend_from_slice(&suffix);
+            self.collect_paths(path, paths);
+        }
+    }
+}
+
+/// Dijkstra's algorithm keeping every cheapest way to reach each node, `None` if no goal is
+/// reachable.
+///
+/// **Note:** Every step must have a positive cost
+pub fn all_shortest_paths<G: Graph>(
+    graph: &G,
+    start: G::Node,
+    is_goal: impl Fn(&G::Node) -> bool,
+) -> Option<AllShortestPaths<G::Node>> {
+    let mut costs = HashMap::from([(start.clone(), 0)]);
+    let mut predecessors: HashMap<G::Node, Vec<G::Node>> = HashMap::new();
+    let mut heap = BinaryHeap::from([State {
+        priority: 0,
+        cost: 0,
+        node: start,
+    }]);
+    let mut best: Option<(u64, Vec<G::Node>)> = None;
+
+    while let Some(State { cost, node, .. }) = heap.pop() {
+        if costs.get(&node).is_some_and(|c| cost > *c) {
+            continue;
+        }
+        if best.as_ref().is_some_and(|(c, _)| cost > *c) {
+            break;
+        }
+        if is_goal(&node) {
+            best.get_or_insert_with(|| (cost, vec![])).1.push(node);
+            continue;
+        }
+
+        for (next, step) in graph.neighbors(&node) {
+            debug_assert!(step > 0, "Steps must have a positive cost");
+            let next_cost = cost + step;
+
+            match costs.get(&next).map(|c| next_cost.cmp(c)) {
+                Some(Ordering::Greater) => {}
+                Some(Ordering::Equal) => predecessors.entry(next).or_default().push(node.clone()),
+                None | Some(Ordering::Less) => {
+                    costs.insert(next.clone(), next_cost);
+                    predecessors.insert(next.clone(), vec![node.clone()]);
+                    heap.push(State {
+                        priority: next_cost,
+                        cost: next_cost,
+                        node: next,
+                    });
+                }
+            }
+        }
+    }
+
+    best.map(|(cost, goals)| AllShortestPaths {
+        cost,
+        goals,
+        predecessors,
+    })
+}
+
+fn reconstruct<N: Clone + Eq + Hash>(parents: &HashMap<N, N>, goal: N) -> Vec<N> {
+    let mut nodes = vec![goal];
+    while let Some(parent) = parents.get(nodes.last().expect("non empty")) {
+        nodes.push(parent.clone());
+    }
+    nodes.reverse();
+
+    nodes
+}
+
+/// A node in the priority queue, ordered such that the lowest priority is popped first.
+struct State<N> {
+    priority: u64,
+    cost: u64,
+    node: N,
+}
+
+impl<N> PartialEq for State<N> {
+    fn eq(&self, other: &Self) -> bool {
+        self.priority == other.priority
+    }
+}
+
+impl<N> Eq for State<N> {}
+
+impl<N> PartialOrd for State<N> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<N> Ord for State<N> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        other.priority.cmp(&self.priority)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        all_shortest_paths, astar, bfs, dijkstra, from_fn, shortest_paths, GridGraph, Path,
+    };
+    use crate::grid::Grid;
+    use crate::math::Vector2;
+
+    const MAZE: &str = r#"
+S.#....
+.##.##.
+...#..E
+.#...#.
+"#;
+
+    fn v(x: isize, y: isize) -> Vector2<isize> {
+        Vector2::new(x, y)
+    }
+
+    #[test]
+    fn test_grid_searches() {
+        let grid = Grid::<char>::parse(MAZE).unwrap();
+        let graph = GridGraph::new(&grid, |_, to| *to != '#');
+        let start = grid.position(&'S').unwrap();
+        let end = grid.position(&'E').unwrap();
+
+        let path = bfs(&graph, start, |p| *p == end).unwrap();
+        assert_eq!(path.cost, 10);
+        assert_eq!(path.nodes.len(), 11);
+        assert_eq!((path.nodes[0], *path.goal()), (start, end));
+        assert!(path
+            .nodes
+            .windows(2)
+            .all(|w| w[0].manhattan_distance(w[1]) == 1));
+
+        assert_eq!(dijkstra(&graph, start, |p| *p == end).unwrap().cost, 10);
+        let heuristic = |p: &Vector2<isize>| p.manhattan_distance(end) as u64;
+        assert_eq!(
+            astar(&graph, start, |p| *p == end, heuristic).unwrap().cost,
+            10
+        );
+
+        assert_eq!(bfs(&graph, start, |p| *p == v(2, 0)), None);
+    }
+
+    #[test]
+    fn test_weighted() {
+        // 0 -> 1 is expensive, going around via 2 and 3 is cheaper
+        let edges = [(0, 1, 10), (0, 2, 1), (2, 3, 2), (3, 1, 3), (1, 4, 1)];
+        let graph = from_fn(|n: &u32| {
+            let n = *n;
+            edges
+                .iter()
+                .filter(move |(from, _, _)| *from == n)
+                .map(|(_, to, cost)| (*to, *cost))
+        });
+
+        assert_eq!(
+            dijkstra(&graph, 0, |n| *n == 4),
+            Some(Path {
+                nodes: vec![0, 2, 3, 1, 4],
+                cost: 7
+            })
+        );
+        assert_eq!(bfs(&graph, 0, |n| *n == 4).unwrap().nodes, vec![0, 1, 4]);
+
+        let paths = shortest_paths(&graph, 0);
+        assert_eq!(paths.cost(&1), Some(6));
+        assert_eq!(paths.cost(&5), None);
+        assert_eq!(paths.path_to(&3).unwrap().nodes, vec![0, 2, 3]);
+        assert_eq!(paths.iter().count(), 5);
+    }
+
+    #[test]
+    fn test_all_shortest_paths() {
+        let grid = Grid::new(3, 3, '.');
+        let graph = GridGraph::new(&grid, |_, _| true);
+
+        let all = all_shortest_paths(&graph, v(0, 0), |p| *p == v(2, 2)).unwrap();
+        assert_eq!(all.cost, 4);
+        assert_eq!(all.count(), 6);
+        assert_eq!(all.nodes().len(), 9);
+
+        let paths = all.paths();
+        assert_eq!(paths.len(), 6);
+        assert!(paths.iter().all(|p| p.len() == 5 && p[0] == v(0, 0)));
+
+        // Either of two goals at the same distance
+        let all = all_shortest_paths(&graph, v(1, 1), |p| p.x == 0 && p.y != 1).unwrap();
+        assert_eq!(all.cost, 2);
+        assert_eq!(all.goals.len(), 2);
+        assert_eq!(all.count(), 4);
+
+        assert!(all_shortest_paths(&graph, v(0, 0), |p| p.x > 2).is_none());
+    }
+}
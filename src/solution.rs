@@ -0,0 +1,122 @@
+use std::any::Any;
+use std::fmt::{self, Display};
+
+use crate::answer::Answer;
+use crate::error::ParseError;
+use crate::{
+    day01, day02, day03, day04, day05, day06, day07, day08, day09, day10, day11, day12, day13,
+    day14, day15, day16, day17, day18, day19, day20, day21, day22, day23, day24,
+};
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Part {
+    One,
+    Two,
+}
+
+impl Part {
+    pub const BOTH: [Part; 2] = [Part::One, Part::Two];
+}
+
+impl Display for Part {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Part::One => write!(f, "1"),
+            Part::Two => write!(f, "2"),
+        }
+    }
+}
+
+/// A solution to one day's puzzle.
+///
+/// The input is parsed once with [`Solution::parse`] and the result is shared by both parts.
+pub trait Solution {
+    /// Parsed puzzle input.
+    type Input;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError>;
+
+    fn part_one(input: &Self::Input) -> Answer;
+
+    fn part_two(input: &Self::Input) -> Answer;
+}
+
+/// Type erased parsed input, produced by [`DynSolution::parse_input`].
+pub struct Parsed(Box<dyn Any>);
+
+/// Object safe version of [`Solution`], used by the [`SOLUTIONS`] registry.
+///
+/// Implemented for every [`Solution`].
+pub trait DynSolution: Sync {
+    fn parse_input(&self, input: &str) -> Result<Parsed, ParseError>;
+
+    /// Solve `part` using input previously parsed by this solution.
+    ///
+    /// **Note:** Panics if `parsed` was produced by a different solution.
+    fn solve_part(&self, parsed: &Parsed, part: Part) -> Answer;
+
+    /// Parse `input` and solve `part`.
+    fn run(&self, input: &str, part: Part) -> Result<Answer, ParseError> {
+        Ok(self.solve_part(&self.parse_input(input)?, part))
+    }
+}
+
+impl<S> DynSolution for S
+where
+    S: Solution + Sync,
+    S::Input: 'static,
+{
+    fn parse_input(&self, input: &str) -> Result<Parsed, ParseError> {
+        Ok(Parsed(Box::new(S::parse(input)?)))
+    }
+
+    fn solve_part(&self, parsed: &Parsed, part: Part) -> Answer {
+        let input = parsed
+            .0
+            .downcast_ref::<S::Input>()
+            .expect("Input parsed by the same solution");
+
+        match part {
+            Part::One => S::part_one(input),
+            Part::Two => S::part_two(input),
+        }
+    }
+}
+
+/// All solutions, indexed by day starting at 0 for day 1.
+pub static SOLUTIONS: [&dyn DynSolution; 24] = [
+    &day01::Day01,
+    &day02::Day02,
+    &day03::Day03,
+    &day04::Day04,
+    &day05::Day05,
+    &day06::Day06,
+    &day07::Day07,
+    &day08::Day08,
+    &day09::Day09,
+    &day10::Day10,
+    &day11::Day11,
+    &day12::Day12,
+    &day13::Day13,
+    &day14::Day14,
+    &day15::Day15,
+    &day16::Day16,
+    &day17::Day17,
+    &day18::Day18,
+    &day19::Day19,
+    &day20::Day20,
+    &day21::Day21,
+    &day22::Day22,
+    &day23::Day23,
+    &day24::Day24,
+];
+
+/// Look up the solution for `day`, days start at 1.
+pub fn for_day(day: u8) -> Option<&'static dyn DynSolution> {
+    SOLUTIONS.get(usize::from(day).checked_sub(1)?).copied()
+}
+
+/// Iterate over every registered solution together with its day.
+pub fn solutions() -> impl Iterator<Item = (u8, &'static dyn DynSolution)> {
+    (1..).zip(SOLUTIONS.iter().copied())
+}
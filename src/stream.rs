@@ -0,0 +1,165 @@
+use std::fmt;
+use std::io::{self, BufRead};
+
+use crate::{day01, day02, day03, day07, Answer, ParseError, Part};
+
+/// A solver for one part that reads its input incrementally, with memory bounded by the
+/// puzzle rather than the size of the input.
+pub type Solver = fn(&mut dyn BufRead) -> Result<Answer, StreamError>;
+
+/// The streaming solvers for `day`, if it has them.
+pub fn for_day(day: u8) -> Option<[Solver; 2]> {
+    let solvers: [Solver; 2] = match day {
+        1 => [|r| day01::star_one_reader(r), |r| day01::star_two_reader(r)],
+        2 => [|r| day02::star_one_reader(r), |r| day02::star_two_reader(r)],
+        3 => [|r| day03::star_one_reader(r), |r| day03::star_two_reader(r)],
+        7 => [|r| day07::star_one_reader(r), |r| day07::star_two_reader(r)],
+        _ => return None,
+    };
+
+    Some(solvers)
+}
+
+/// The streaming solver for one `part` of `day`, if it has one.
+pub fn for_part(day: u8, part: Part) -> Option<Solver> {
+    let [one, two] = for_day(day)?;
+
+    Some(match part {
+        Part::One => one,
+        Part::Two => two,
+    })
+}
+
+#[derive(Debug)]
+pub enum StreamError {
+    Io(io::Error),
+    Parse(ParseError),
+}
+
+impl fmt::Display for StreamError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Io(e) => write!(f, "unable to read input: {e}"),
+            Self::Parse(e) => write!(f, "{e}"),
+        }
+    }
+}
+
+impl std::error::Error for StreamError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::Io(e) => Some(e),
+            Self::Parse(e) => Some(e),
+        }
+    }
+}
+
+impl From<io::Error> for StreamError {
+    fn from(e: io::Error) -> Self {
+        Self::Io(e)
+    }
+}
+
+impl From<ParseError> for StreamError {
+    fn from(e: ParseError) -> Self {
+        Self::Parse(e)
+    }
+}
+
+/// Call `f` with every line of `reader` that isn't blank, trimmed. Only one line is held in
+/// memory at a time.
+///
+/// `f` parses a single line, its errors are moved to the line's position within the stream.
+pub fn for_each_line(
+    mut reader: impl BufRead,
+    mut f: impl FnMut(&str) -> Result<(), ParseError>,
+) -> Result<(), StreamError> {
+    let mut buffer = String::new();
+
+    for number in 1.. {
+        buffer.clear();
+        if reader.read_line(&mut buffer)? == 0 {
+            break;
+        }
+
+        let line = buffer.trim();
+        if line.is_empty() {
+            continue;
+        }
+
+        f(line).map_err(|mut e| {
+            let indent = buffer.len() - buffer.trim_start().len();
+            e.line += number - 1;
+            e.column += buffer[..indent].chars().count();
+            e
+        })?;
+    }
+
+    Ok(())
+}
+
+/// Call `f` with every byte of `reader`, in chunks as they are read.
+pub fn for_each_byte(mut reader: impl BufRead, mut f: impl FnMut(u8)) -> Result<(), StreamError> {
+    loop {
+        let chunk = reader.fill_buf()?;
+        if chunk.is_empty() {
+            return Ok(());
+        }
+
+        chunk.iter().for_each(|b| f(*b));
+        let len = chunk.len();
+        reader.consume(len);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::BufReader;
+
+    use super::{for_day, for_each_byte, for_each_line, StreamError};
+    use crate::day01::Day01;
+    use crate::parse::{self, integer};
+    use crate::{ParseError, Solution};
+
+    #[test]
+    fn test_for_each_line() {
+        let mut sum = 0;
+        for_each_line("1\n\n  2 \n3".as_bytes(), |line| {
+            sum += parse::complete(line, integer::<i64>())?;
+            Ok(())
+        })
+        .unwrap();
+        assert_eq!(sum, 6);
+
+        let error = for_each_line("1\n\n  2x \n3".as_bytes(), |line| {
+            parse::complete(line, integer::<i64>()).map(|_| ())
+        });
+        match error {
+            Err(StreamError::Parse(e)) => assert_eq!(e, ParseError::new(3, 3, "2x", "i64")),
+            other => panic!("Expected parse error, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn test_for_each_byte() {
+        // A tiny buffer forces several reads
+        let reader = BufReader::with_capacity(3, "hello world".as_bytes());
+        let mut bytes = vec![];
+        for_each_byte(reader, |b| bytes.push(b)).unwrap();
+
+        assert_eq!(bytes, b"hello world");
+    }
+
+    #[test]
+    fn test_solvers() {
+        let input = "3   4\n4   3\n2   5\n1   3\n3   9\n3   3\n";
+        let [one, two] = for_day(1).unwrap();
+
+        assert_eq!(
+            one(&mut input.as_bytes()).unwrap(),
+            Day01::part_one(&Day01::parse(input).unwrap())
+        );
+        assert_eq!(two(&mut input.as_bytes()).unwrap(), 31);
+        assert!(for_day(4).is_none());
+    }
+}
@@ -0,0 +1,341 @@
+use std::fmt::Write as _;
+use std::fs::File;
+use std::io::{self, BufWriter, Write};
+use std::path::{Path, PathBuf};
+
+use crate::grid::Grid;
+use crate::math::Vector2;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Color {
+    pub r: u8,
+    pub g: u8,
+    pub b: u8,
+}
+
+impl Color {
+    pub const BLACK: Self = Self::new(0, 0, 0);
+    pub const WHITE: Self = Self::new(255, 255, 255);
+    pub const GRAY: Self = Self::new(128, 128, 128);
+    pub const RED: Self = Self::new(220, 50, 47);
+    pub const GREEN: Self = Self::new(133, 153, 0);
+    pub const BLUE: Self = Self::new(38, 139, 210);
+    pub const YELLOW: Self = Self::new(181, 137, 0);
+
+    pub const fn new(r: u8, g: u8, b: u8) -> Self {
+        Self { r, g, b }
+    }
+
+    /// The perceived brightness, for grayscale images.
+    pub fn luma(&self) -> u8 {
+        let luma =
+            0.299 * f64::from(self.r) + 0.587 * f64::from(self.g) + 0.114 * f64::from(self.b);
+
+        luma.round() as u8
+    }
+}
+
+/// How a single cell is drawn in the terminal.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Style {
+    pub glyph: char,
+    pub foreground: Option<Color>,
+    pub background: Option<Color>,
+}
+
+impl Style {
+    pub fn new(glyph: char) -> Self {
+        Self {
+            glyph,
+            foreground: None,
+            background: None,
+        }
+    }
+
+    pub fn fg(self, color: Color) -> Self {
+        Self {
+            foreground: Some(color),
+            ..self
+        }
+    }
+
+    pub fn bg(self, color: Color) -> Self {
+        Self {
+            background: Some(color),
+            ..self
+        }
+    }
+
+    fn is_plain(&self) -> bool {
+        self.foreground.is_none() && self.background.is_none()
+    }
+}
+
+impl From<char> for Style {
+    fn from(glyph: char) -> Self {
+        Self::new(glyph)
+    }
+}
+
+/// Render `grid` for a terminal with 24-bit ANSI colors, one line per row.
+///
+/// Escape codes are only emitted when the style changes and every row ends with a reset, so the
+/// output can be printed line by line.
+pub fn ansi<T>(grid: &Grid<T>, style: impl Fn(Vector2<isize>, &T) -> Style) -> String {
+    let mut out = String::new();
+
+    for y in 0..grid.height() as isize {
+        let mut current: Option<Style> = None;
+        for x in 0..grid.width() as isize {
+            let position = Vector2::new(x, y);
+            let cell = style(position, &grid[position]);
+
+            let changed = current
+                .is_none_or(|c| (c.foreground, c.background) != (cell.foreground, cell.background));
+            if changed {
+                if current.is_some_and(|c| !c.is_plain()) {
+                    out.push_str("\x1b[0m");
+                }
+                if let Some(Color { r, g, b }) = cell.foreground {
+                    let _ = write!(out, "\x1b[38;2;{r};{g};{b}m");
+                }
+                if let Some(Color { r, g, b }) = cell.background {
+                    let _ = write!(out, "\x1b[48;2;{r};{g};{b}m");
+                }
+            }
+            out.push(cell.glyph);
+            current = Some(cell);
+        }
+
+        if current.is_some_and(|c| !c.is_plain()) {
+            out.push_str("\x1b[0m");
+        }
+        out.push('\n');
+    }
+
+    out
+}
+
+/// Write `grid` as a binary PPM image with every cell drawn as a `scale` by `scale` square.
+///
+/// **Note:** Panics if `scale` is zero
+pub fn write_ppm<T>(
+    mut writer: impl Write,
+    grid: &Grid<T>,
+    scale: usize,
+    color: impl Fn(Vector2<isize>, &T) -> Color,
+) -> io::Result<()> {
+    write_image(&mut writer, "P6", grid, scale, |p, c| {
+        let Color { r, g, b } = color(p, c);
+        vec![r, g, b]
+    })
+}
+
+/// Write `grid` as a binary PGM image, i.e. grayscale, see [`write_ppm`].
+///
+/// **Note:** Panics if `scale` is zero
+pub fn write_pgm<T>(
+    mut writer: impl Write,
+    grid: &Grid<T>,
+    scale: usize,
+    shade: impl Fn(Vector2<isize>, &T) -> u8,
+) -> io::Result<()> {
+    write_image(&mut writer, "P5", grid, scale, |p, c| vec![shade(p, c)])
+}
+
+fn write_image<T>(
+    writer: &mut impl Write,
+    magic: &str,
+    grid: &Grid<T>,
+    scale: usize,
+    pixel: impl Fn(Vector2<isize>, &T) -> Vec<u8>,
+) -> io::Result<()> {
+    assert!(scale > 0, "Scale must be positive");
+
+    writeln!(
+        writer,
+        "{magic}\n{} {}\n255",
+        grid.width() * scale,
+        grid.height() * scale
+    )?;
+
+    for y in 0..grid.height() as isize {
+        let row: Vec<u8> = (0..grid.width() as isize)
+            .flat_map(|x| {
+                let position = Vector2::new(x, y);
+                pixel(position, &grid[position]).repeat(scale)
+            })
+            .collect();
+        for _ in 0..scale {
+            writer.write_all(&row)?;
+        }
+    }
+
+    writer.flush()
+}
+
+/// The frames of a simulation, e.g. a guard walking through a lab.
+///
+/// Only every `interval`th frame is kept to limit memory use for long simulations, frames are
+/// built lazily so skipped ones cost nothing.
+#[derive(Debug, Clone)]
+pub struct Recorder<T> {
+    frames: Vec<Grid<T>>,
+    interval: usize,
+    offered: usize,
+}
+
+impl<T> Recorder<T> {
+    /// A recorder that keeps every frame.
+    pub fn new() -> Self {
+        Self::every(1)
+    }
+
+    /// A recorder that keeps the first frame and every `interval`th after it.
+    ///
+    /// **Note:** Panics if `interval` is zero
+    pub fn every(interval: usize) -> Self {
+        assert!(interval > 0, "Interval must be positive");
+
+        Self {
+            frames: vec![],
+            interval,
+            offered: 0,
+        }
+    }
+
+    pub fn record(&mut self, frame: impl FnOnce() -> Grid<T>) {
+        if self.offered.is_multiple_of(self.interval) {
+            self.frames.push(frame());
+        }
+        self.offered += 1;
+    }
+
+    /// Record the final frame, it is kept even if the interval would skip it.
+    pub fn finish(&mut self, frame: impl FnOnce() -> Grid<T>) {
+        self.frames.push(frame());
+        self.offered += 1;
+    }
+
+    pub fn frames(&self) -> &[Grid<T>] {
+        &self.frames
+    }
+
+    /// The number of kept frames.
+    pub fn len(&self) -> usize {
+        self.frames.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.frames.is_empty()
+    }
+
+    /// Write every frame as `{prefix}-0000.ppm`, `{prefix}-0001.ppm`, ... to `dir`, e.g. to be
+    /// turned into a video with ffmpeg. Returns the written paths.
+    pub fn write_ppm(
+        &self,
+        dir: &Path,
+        prefix: &str,
+        scale: usize,
+        color: impl Fn(Vector2<isize>, &T) -> Color,
+    ) -> io::Result<Vec<PathBuf>> {
+        self.frames
+            .iter()
+            .enumerate()
+            .map(|(i, frame)| {
+                let path = dir.join(format!("{prefix}-{i:04}.ppm"));
+                write_ppm(BufWriter::new(File::create(&path)?), frame, scale, &color)?;
+
+                Ok(path)
+            })
+            .collect()
+    }
+}
+
+impl<T> Default for Recorder<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+
+    use super::{ansi, write_pgm, write_ppm, Color, Recorder, Style};
+    use crate::grid::Grid;
+    use crate::math::Vector2;
+
+    #[test]
+    fn test_ansi() {
+        let grid = Grid::<char>::parse("#.\n..").unwrap();
+        let style = |_, c: &char| match c {
+            '#' => Style::new('#').fg(Color::RED),
+            _ => Style::from(*c),
+        };
+
+        assert_eq!(ansi(&grid, style), "\x1b[38;2;220;50;47m#\x1b[0m.\n..\n");
+        assert_eq!(ansi(&grid, |_, c| Style::from(*c)), grid.to_string());
+
+        let highlighted = ansi(&grid, |p, c| {
+            if p.y == 1 {
+                Style::new(*c).bg(Color::BLUE)
+            } else {
+                Style::new(*c)
+            }
+        });
+        assert_eq!(highlighted, "#.\n\x1b[48;2;38;139;210m..\x1b[0m\n");
+    }
+
+    #[test]
+    fn test_images() {
+        let grid = Grid::from_cells(2, vec![true, false]);
+
+        let mut ppm = vec![];
+        write_ppm(&mut ppm, &grid, 1, |_, on| {
+            if *on {
+                Color::WHITE
+            } else {
+                Color::BLACK
+            }
+        })
+        .unwrap();
+        assert_eq!(ppm, b"P6\n2 1\n255\n\xff\xff\xff\x00\x00\x00");
+
+        let mut pgm = vec![];
+        write_pgm(&mut pgm, &grid, 2, |_, on| if *on { 200 } else { 0 }).unwrap();
+        assert_eq!(pgm, b"P5\n4 2\n255\n\xc8\xc8\x00\x00\xc8\xc8\x00\x00");
+
+        assert_eq!(Color::WHITE.luma(), 255);
+        assert_eq!(Color::new(0, 255, 0).luma(), 150);
+    }
+
+    #[test]
+    fn test_recorder() {
+        let mut recorder = Recorder::every(3);
+        for i in 0..7 {
+            recorder.record(|| Grid::new(1, 1, i));
+        }
+        recorder.finish(|| Grid::new(1, 1, 7));
+
+        let kept: Vec<_> = recorder
+            .frames()
+            .iter()
+            .map(|f| f[Vector2::new(0, 0)])
+            .collect();
+        assert_eq!(kept, vec![0, 3, 6, 7]);
+
+        let dir = std::env::temp_dir().join(format!("aoc-viz-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let paths = recorder
+            .write_ppm(&dir, "frame", 2, |_, _| Color::GRAY)
+            .unwrap();
+        assert_eq!(paths.len(), 4);
+        assert_eq!(paths[3].file_name().unwrap(), "frame-0003.ppm");
+        assert_eq!(
+            fs::read(&paths[0]).unwrap().len(),
+            "P6\n2 2\n255\n".len() + 2 * 2 * 3
+        );
+        fs::remove_dir_all(dir).unwrap();
+    }
+}